@@ -0,0 +1,267 @@
+use crate::{
+    checked::Checker,
+    chunk_mut::{ChunkMut, ChunkMutParts},
+    chunks_mut::ArrayIterMut,
+    common::*,
+    error::TryUnwrapResult,
+    owner::Owner,
+    raw::RawSlice,
+    tracking::Tracker,
+};
+use core::{mem::ManuallyDrop, ptr};
+
+/// A mutable sub-slice reference-counted reference to a slice-like data, viewed as a slice of
+/// arrays of `N` elements.
+///
+/// It is created by [into_array_chunks()](ChunkMut::into_array_chunks).
+#[derive(Debug)]
+pub struct ArrayChunkMut<'a, S, T, const N: usize>
+where
+    S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
+    T: Send + Sync,
+{
+    // unregistered before the owner is dropped, as its address may be reused by a new owner
+    pub(super) tracker: Tracker,
+    pub(super) checker: Checker,
+    pub(super) owner: Owner<S>,
+    pub(super) raw: RawSlice<T>,
+    pub(super) start: usize,
+    pub(super) end: usize,
+    pub(super) _phantom: PhantomData<&'a S>,
+}
+
+impl<'a, S, T, const N: usize> ArrayChunkMut<'a, S, T, N>
+where
+    S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
+    T: Send + Sync,
+{
+    /// Converts back to a chunk of flattened elements.
+    pub fn into_chunk(self) -> ChunkMut<'a, S, T> {
+        let ChunkMutParts {
+            owner,
+            raw,
+            start,
+            end,
+            checker,
+            ..
+        } = self.into_parts();
+        checker.release();
+        unsafe { ChunkMut::from_owner_range(owner, raw, start, end) }
+    }
+
+    /// Returns an iterator of owned references to each array of the slice.
+    pub fn into_iter_owned(self) -> ArrayIterMut<'a, S, T, N> {
+        let ChunkMutParts {
+            owner,
+            raw,
+            start,
+            end,
+            ..
+        } = self.into_parts();
+
+        ArrayIterMut {
+            owner,
+            raw,
+            index: start,
+            end,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Gets the reference count on the owning data.
+    pub fn ref_count(&self) -> usize {
+        self.owner.ref_count()
+    }
+
+    /// Lists the chunks referring to the owning data, which are still alive.
+    ///
+    /// Chunk iterators and owned elements referring to the data are not listed.
+    #[cfg(feature = "tracking")]
+    pub fn live_chunks(&self) -> Vec<ChunkInfo> {
+        self.owner.live_chunks()
+    }
+
+    /// Cancels the jobs over the owning data. See [cancel()](ChunkMut::cancel).
+    pub fn cancel(&self) {
+        self.owner.cancel()
+    }
+
+    /// Checks if the jobs over the owning data are cancelled by any chunk.
+    pub fn is_cancelled(&self) -> bool {
+        self.owner.is_cancelled()
+    }
+
+    /// Converts to the reference-counted owner.
+    ///
+    /// The method succeeds if no chunk or chunk iterator refers to the owner, which could
+    /// otherwise mutate the data behind the returned [Arc]. Otherwise, it returns the chunk
+    /// intact.
+    pub fn into_arc_owner(self) -> TryUnwrapResult<Arc<S>, Self> {
+        let ChunkMutParts {
+            owner,
+            raw,
+            start,
+            end,
+            tracker,
+            checker,
+            ..
+        } = self.into_parts();
+        owner.try_recover_arc().map_err(|err| {
+            err.map_shared(|owner| Self {
+                owner,
+                raw,
+                start,
+                end,
+                tracker,
+                checker,
+                _phantom: PhantomData,
+            })
+        })
+    }
+
+    /// Tries to recover the owning data.
+    ///
+    /// The method succeeds if the referencing chunk iterator and all chunks are dropped.
+    /// Otherwise, it returns the guard intact.
+    pub fn try_unwrap_owner(self) -> TryUnwrapResult<S, Self>
+    where
+        S: Sized,
+    {
+        let ChunkMutParts {
+            owner,
+            raw,
+            start,
+            end,
+            tracker,
+            checker,
+            ..
+        } = self.into_parts();
+        owner.try_recover().map_err(|err| {
+            err.map_shared(|owner| Self {
+                owner,
+                raw,
+                start,
+                end,
+                tracker,
+                checker,
+                _phantom: PhantomData,
+            })
+        })
+    }
+
+    /// Moves the fields out of the chunk, which is consumed without being marked poisoned.
+    fn into_parts(self) -> ChunkMutParts<S, T> {
+        let this = ManuallyDrop::new(self);
+
+        // each field is read exactly once from the forgotten chunk
+        unsafe {
+            ChunkMutParts {
+                owner: ptr::read(&this.owner),
+                raw: this.raw,
+                start: this.start,
+                end: this.end,
+                partition: None,
+                tracker: ptr::read(&this.tracker),
+                checker: ptr::read(&this.checker),
+            }
+        }
+    }
+}
+
+impl<'a, S, T, const N: usize> Drop for ArrayChunkMut<'a, S, T, N>
+where
+    S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
+    T: Send + Sync,
+{
+    fn drop(&mut self) {
+        self.owner.poison_on_panic(self.start..self.end);
+    }
+}
+
+unsafe impl<'a, S, T, const N: usize> Send for ArrayChunkMut<'a, S, T, N>
+where
+    S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
+    T: Send + Sync,
+{
+}
+
+unsafe impl<'a, S, T, const N: usize> Sync for ArrayChunkMut<'a, S, T, N>
+where
+    S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
+    T: Send + Sync,
+{
+}
+
+impl<'a, S, T, const N: usize> AsRef<[[T; N]]> for ArrayChunkMut<'a, S, T, N>
+where
+    S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
+    T: Send + Sync,
+{
+    fn as_ref(&self) -> &[[T; N]] {
+        self.deref()
+    }
+}
+
+impl<'a, S, T, const N: usize> AsMut<[[T; N]]> for ArrayChunkMut<'a, S, T, N>
+where
+    S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
+    T: Send + Sync,
+{
+    fn as_mut(&mut self) -> &mut [[T; N]] {
+        self.deref_mut()
+    }
+}
+
+impl<'a, S, T, const N: usize> Deref for ArrayChunkMut<'a, S, T, N>
+where
+    S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
+    T: Send + Sync,
+{
+    type Target = [[T; N]];
+
+    fn deref(&self) -> &Self::Target {
+        unsafe {
+            let slice = self.raw.slice(self.start, self.end);
+            slice::from_raw_parts(slice.as_ptr().cast(), slice.len() / N)
+        }
+    }
+}
+
+impl<'a, S, T, const N: usize> DerefMut for ArrayChunkMut<'a, S, T, N>
+where
+    S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
+    T: Send + Sync,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe {
+            let slice = self.raw.slice_mut(self.start, self.end);
+            slice::from_raw_parts_mut(slice.as_mut_ptr().cast(), slice.len() / N)
+        }
+    }
+}
+
+impl<'a, S, T, const N: usize> IntoIterator for &'a ArrayChunkMut<'_, S, T, N>
+where
+    S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
+    T: Send + Sync,
+{
+    type Item = &'a [T; N];
+    type IntoIter = slice::Iter<'a, [T; N]>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.deref().iter()
+    }
+}
+
+impl<'a, S, T, const N: usize> IntoIterator for &'a mut ArrayChunkMut<'_, S, T, N>
+where
+    S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
+    T: Send + Sync,
+{
+    type Item = &'a mut [T; N];
+    type IntoIter = slice::IterMut<'a, [T; N]>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.deref_mut().iter_mut()
+    }
+}
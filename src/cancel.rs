@@ -0,0 +1,58 @@
+//! The cancellation of jobs over an owner, shared by all chunks and chunk iterators of the
+//! owner.
+//!
+//! The flag is kept in the state shared by the references to the owner, and is dropped
+//! along with the last reference.
+
+use crate::{common::*, owner::OwnerState};
+
+/// An iterator adapter that stops yielding chunks once the owner is cancelled.
+///
+/// It is created by the `cancellable()` methods on chunk iterators. The chunks already
+/// handed out are not affected, and workers are expected to check
+/// [is_cancelled()](crate::ChunkMut::is_cancelled) on them during long jobs.
+#[derive(Debug)]
+pub struct Cancellable<I> {
+    iter: I,
+    state: Arc<OwnerState>,
+}
+
+impl<I> Cancellable<I> {
+    pub(crate) fn new(state: Arc<OwnerState>, iter: I) -> Self {
+        Self { iter, state }
+    }
+
+    /// Cancels the jobs over the owner, so that no more chunks are yielded.
+    pub fn cancel(&self) {
+        self.state.cancel();
+    }
+
+    /// Checks if the owner is cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.state.is_cancelled()
+    }
+
+    /// Takes back the wrapped iterator.
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+impl<I> Iterator for Cancellable<I>
+where
+    I: Iterator,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_cancelled() {
+            return None;
+        }
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, upper) = self.iter.size_hint();
+        (0, upper)
+    }
+}
@@ -0,0 +1,111 @@
+//! The runtime overlap checker on mutable chunks, enabled by the `checked` feature.
+//!
+//! Each mutable chunk holds a [Checker] that registers its range in the interval set of its
+//! owner, and panics if the range overlaps with any live mutable chunk. The range is
+//! unregistered when the chunk is dropped. Without the feature, the checker is a zero-sized
+//! no-op.
+
+use core::ops::Range;
+
+#[cfg(feature = "checked")]
+pub use imp::*;
+
+#[cfg(feature = "checked")]
+mod imp {
+    use super::*;
+    use crate::utils;
+    use alloc::{boxed::Box, collections::BTreeMap};
+    use std::sync::Mutex;
+
+    /// The live ranges indexed by owner address and then by start offset.
+    type Intervals = BTreeMap<usize, BTreeMap<usize, usize>>;
+
+    static INTERVALS: Mutex<Intervals> = Mutex::new(BTreeMap::new());
+
+    /// The registration of a mutable chunk in the interval set, which is removed when dropped.
+    ///
+    /// It is exported only to test the checker, since no chunk created through the public
+    /// API can overlap another.
+    #[derive(Debug)]
+    pub struct Checker {
+        // boxed to keep the chunk size, as the lock dominates the cost anyway
+        key: Option<Box<(usize, usize)>>,
+    }
+
+    impl Checker {
+        /// Registers the range of a mutable chunk.
+        ///
+        /// # Panics
+        /// The method panics if the range overlaps with any live mutable chunk on the owner.
+        pub fn new(owner: usize, range: Range<usize>) -> Self {
+            // empty chunks never alias
+            if range.is_empty() {
+                return Self { key: None };
+            }
+
+            let overlapped = {
+                let mut intervals = utils::lock(&INTERVALS);
+                let ranges = intervals.entry(owner).or_default();
+
+                // the live ranges are disjoint, so only the last one starting before the end
+                // of the range can overlap with it
+                let overlapped = ranges
+                    .range(..range.end)
+                    .next_back()
+                    .filter(|&(_, &end)| end > range.start)
+                    .map(|(&start, &end)| start..end);
+
+                if overlapped.is_none() {
+                    ranges.insert(range.start, range.end);
+                }
+                overlapped
+            };
+
+            if let Some(overlapped) = overlapped {
+                panic!(
+                    "mutable chunk {:?} overlaps with live mutable chunk {:?} on owner {:#x}",
+                    range, overlapped, owner
+                );
+            }
+
+            Self {
+                key: Some(Box::new((owner, range.start))),
+            }
+        }
+
+        /// Unregisters the range before the chunk is dropped, so that the range can be taken
+        /// over by new chunks in the meantime.
+        pub fn release(self) {}
+    }
+
+    impl Drop for Checker {
+        fn drop(&mut self) {
+            let (owner, start) = match self.key.take() {
+                Some(key) => *key,
+                None => return,
+            };
+
+            let mut intervals = utils::lock(&INTERVALS);
+            if let Some(ranges) = intervals.get_mut(&owner) {
+                ranges.remove(&start);
+                if ranges.is_empty() {
+                    intervals.remove(&owner);
+                }
+            }
+        }
+    }
+}
+
+/// The registration of a mutable chunk, which does nothing without the `checked` feature.
+#[cfg(not(feature = "checked"))]
+#[derive(Debug)]
+pub(crate) struct Checker;
+
+#[cfg(not(feature = "checked"))]
+impl Checker {
+    pub fn new(_owner: usize, _range: Range<usize>) -> Self {
+        Checker
+    }
+
+    pub fn release(self) {}
+}
@@ -1,41 +1,53 @@
 use crate::{
-    chunks::{EvenChunks, Iter, SizedChunks, Windows},
+    chunks::{
+        ChunkBy, EvenChunks, ExactChunks, Iter, RChunks, RChunksExact, SizedChunks, Split,
+        SplitInclusive, SplitN, Windows,
+    },
     common::*,
+    error::ChunkError,
+    on_release::OnRelease,
+    owner::{Owner, OwnerState},
+    partition::{Partition, PartitionCounter},
+    poison::Poisoned,
+    raw::RawSlice,
+    tracking::Tracker,
+    utils::{self, Discontinuity},
+    weak_chunk::WeakChunk,
 };
-use std::{ops::RangeBounds, slice::SliceIndex};
+use core::ops::{Range, RangeBounds, RangeFrom};
 
 /// A mutable sub-slice reference-counted reference to a slice-like data.
 #[derive(Debug)]
 pub struct Chunk<'a, S, T>
 where
-    S: AsRef<[T]> + Send + Sync + 'a,
+    S: ChunkOwner<T> + Send + Sync + ?Sized + 'a,
     T: Send + Sync,
 {
-    pub(super) owner: Arc<S>,
-    pub(super) slice: NonNull<[T]>,
+    // unregistered before the owner is dropped, as its address may be reused by a new owner
+    pub(super) tracker: Tracker,
+    pub(super) owner: Owner<S>,
+    pub(super) raw: RawSlice<T>,
+    pub(super) start: usize,
+    pub(super) end: usize,
+    pub(super) partition: Option<Partition>,
     pub(super) _phantom: PhantomData<&'a S>,
 }
 
 impl<'a, S, T> Chunk<'a, S, T>
 where
-    S: AsRef<[T]> + Send + Sync + 'a,
+    S: ChunkOwner<T> + Send + Sync + ?Sized + 'a,
     T: Send + Sync,
 {
-    pub fn new(owner: S) -> Self {
+    pub fn new(owner: S) -> Self
+    where
+        S: Sized,
+    {
         Self::from_arc(Arc::new(owner))
     }
 
     pub fn from_arc(owner: Arc<S>) -> Self {
-        unsafe {
-            let ptr = Arc::as_ptr(&owner);
-            let slice: &[T] = ptr.as_ref().unwrap().as_ref();
-            let slice = NonNull::new_unchecked(slice as *const [T] as *mut [T]);
-            Self {
-                owner,
-                slice,
-                _phantom: PhantomData,
-            }
-        }
+        let raw = RawSlice::from_ref(owner.as_slice());
+        Self::from_owner_range(Owner::new(owner), raw, 0, raw.len())
     }
 
     /// Splits the chunk into two sub-chunks, divided at specified index.
@@ -43,25 +55,120 @@ where
     /// # Panics
     /// The method panics if the index is out of bound.
     pub fn split_at(self, index: usize) -> (Chunk<'a, S, T>, Chunk<'a, S, T>) {
-        unsafe {
-            let owner = self.owner;
-            let slice: &[T] = self.slice.as_ref();
-            let lslice = NonNull::new_unchecked(&slice[0..index] as *const [T] as *mut [T]);
-            let rslice = NonNull::new_unchecked(&slice[index..] as *const [T] as *mut [T]);
-
-            (
-                Chunk {
-                    owner: owner.clone(),
-                    slice: lslice,
-                    _phantom: PhantomData,
-                },
-                Chunk {
-                    owner,
-                    slice: rslice,
-                    _phantom: PhantomData,
-                },
-            )
+        match self.try_split_at(index) {
+            Ok(pair) => pair,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Splits the chunk into two sub-chunks, divided at specified index.
+    ///
+    /// It returns the chunk back in the error if the index is out of bound.
+    pub fn try_split_at(self, index: usize) -> Result<(Self, Self), ChunkError<Self>> {
+        let len = self.len();
+        if index > len {
+            return Err(ChunkError::OutOfBounds {
+                index,
+                len,
+                chunk: self,
+            });
+        }
+
+        let Self {
+            owner,
+            raw,
+            start,
+            end,
+            ..
+        } = self;
+        let mid = start + index;
+
+        Ok((
+            Chunk::from_owner_range(owner.clone(), raw, start, mid),
+            Chunk::from_owner_range(owner, raw, mid, end),
+        ))
+    }
+
+    /// Splits the chunk into sub-chunks, divided at each of the specified indices.
+    ///
+    /// It returns `indices.len() + 1` chunks. The indices must be sorted in ascending order
+    /// and must not exceed the length of the chunk, otherwise the chunk is returned
+    /// back in the error.
+    pub fn split_at_indices(self, indices: &[usize]) -> Result<Vec<Self>, ChunkError<Self>> {
+        let len = self.len();
+        let mut prev = 0;
+
+        for &index in indices {
+            if index > len {
+                return Err(ChunkError::OutOfBounds {
+                    index,
+                    len,
+                    chunk: self,
+                });
+            }
+            if index < prev {
+                return Err(ChunkError::UnsortedIndices {
+                    prev,
+                    next: index,
+                    chunk: self,
+                });
+            }
+            prev = index;
+        }
+
+        let mut chunks = Vec::with_capacity(indices.len() + 1);
+        let mut prev = 0;
+        let rest = indices.iter().fold(self, |rest, &index| {
+            let (lchunk, rchunk) = rest.split_at(index - prev);
+            chunks.push(lchunk);
+            prev = index;
+            rchunk
+        });
+        chunks.push(rest);
+
+        let mut counter = PartitionCounter::new(chunks.len());
+        chunks
+            .iter_mut()
+            .for_each(|chunk| chunk.partition = Some(counter.next_partition()));
+
+        Ok(chunks)
+    }
+
+    /// Splits the chunk into consecutive sub-chunks with specified lengths.
+    ///
+    /// It returns `lengths.len()` chunks. The lengths must sum up to the length of the
+    /// chunk, otherwise the chunk is returned back in the error.
+    pub fn split_by_lengths(self, lengths: &[usize]) -> Result<Vec<Self>, ChunkError<Self>> {
+        let len = self.len();
+        let total = lengths
+            .iter()
+            .try_fold(0usize, |total, &length| total.checked_add(length));
+
+        match total {
+            Some(total) if total == len => {}
+            total => {
+                return Err(ChunkError::LengthMismatch {
+                    total: total.unwrap_or(usize::MAX),
+                    len,
+                    chunk: self,
+                });
+            }
         }
+
+        let mut chunks = Vec::with_capacity(lengths.len());
+        let rest = lengths.iter().fold(self, |rest, &length| {
+            let (lchunk, rchunk) = rest.split_at(length);
+            chunks.push(lchunk);
+            rchunk
+        });
+        debug_assert!(rest.is_empty());
+
+        let mut counter = PartitionCounter::new(chunks.len());
+        chunks
+            .iter_mut()
+            .for_each(|chunk| chunk.partition = Some(counter.next_partition()));
+
+        Ok(chunks)
     }
 
     /// Returns an iterator of fixed-sized chunks of the refencing slice.
@@ -75,26 +182,195 @@ where
     /// # Panics
     /// The method panics if `chunk_size` is zero and slice length is not zero.
     pub fn into_sized_chunks(self, chunk_size: usize) -> SizedChunks<'a, S, T> {
-        assert!(mem::size_of::<T>() > 0, "zero-sized type is not allowed");
-
-        unsafe {
-            let start = self.start_index();
-            let owner = self.owner;
-            let slice_len = self.slice.as_ref().len();
-
-            assert!(
-                slice_len == 0 || chunk_size > 0,
-                "chunk_size must be positive for non-empty slice"
-            );
-
-            SizedChunks {
-                chunk_size,
-                index: start,
-                end: start + slice_len,
-                owner,
-                _phantom: PhantomData,
-            }
+        match self.try_into_sized_chunks(chunk_size) {
+            Ok(chunks) => chunks,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Returns an iterator of fixed-sized chunks of the refencing slice.
+    ///
+    /// It is the fallible version of [into_sized_chunks()](Chunk::into_sized_chunks), which
+    /// returns the chunk back in the error if `chunk_size` is zero and slice length is not
+    /// zero.
+    pub fn try_into_sized_chunks(
+        self,
+        chunk_size: usize,
+    ) -> Result<SizedChunks<'a, S, T>, ChunkError<Self>> {
+        if !self.is_empty() && chunk_size == 0 {
+            return Err(ChunkError::ZeroChunkSize { chunk: self });
+        }
+
+        let start = self.start;
+        let slice_len = self.len();
+        let Self { owner, raw, .. } = self;
+
+        let count = if chunk_size > 0 {
+            slice_len.div_ceil(chunk_size)
+        } else {
+            0
+        };
+        Ok(SizedChunks {
+            partition: PartitionCounter::new(count),
+            chunk_size,
+            index: start,
+            end: start + slice_len,
+            owner,
+            raw,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Returns an iterator of fixed-sized chunks of the referencing slice.
+    ///
+    /// Each chunk has exactly `chunk_size` elements. The trailing elements that do not fill
+    /// a whole chunk are left out, and can be retrieved as a chunk by
+    /// [into_remainder()](ExactChunks::into_remainder).
+    ///
+    /// # Panics
+    /// The method panics if `chunk_size` is zero and slice length is not zero.
+    pub fn into_exact_chunks(self, chunk_size: usize) -> ExactChunks<'a, S, T> {
+        match self.try_into_exact_chunks(chunk_size) {
+            Ok(chunks) => chunks,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Returns an iterator of fixed-sized chunks of the referencing slice.
+    ///
+    /// It is the fallible version of [into_exact_chunks()](Chunk::into_exact_chunks), which returns the chunk back in the
+    /// error if `chunk_size` is zero and slice length is not zero.
+    pub fn try_into_exact_chunks(
+        self,
+        chunk_size: usize,
+    ) -> Result<ExactChunks<'a, S, T>, ChunkError<Self>> {
+        if !self.is_empty() && chunk_size == 0 {
+            return Err(ChunkError::ZeroChunkSize { chunk: self });
         }
+
+        let start = self.start;
+        let slice_len = self.len();
+        let Self { owner, raw, .. } = self;
+
+        let rem_len = if chunk_size > 0 {
+            slice_len % chunk_size
+        } else {
+            0
+        };
+        let end = start + slice_len - rem_len;
+
+        let count = slice_len.checked_div(chunk_size).unwrap_or(0);
+        Ok(ExactChunks {
+            partition: PartitionCounter::new(count),
+            chunk_size,
+            index: start,
+            end,
+            rem_end: end + rem_len,
+            owner,
+            raw,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Returns an iterator of fixed-sized chunks of the referencing slice, starting at the end
+    /// of the slice.
+    ///
+    /// Each chunk has `chunk_size` elements, expect the last chunk, which covers the beginning
+    /// of the slice, maybe shorter if there aren't enough elements.
+    ///
+    /// # Panics
+    /// The method panics if `chunk_size` is zero and slice length is not zero.
+    pub fn into_rchunks(self, chunk_size: usize) -> RChunks<'a, S, T> {
+        match self.try_into_rchunks(chunk_size) {
+            Ok(chunks) => chunks,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Returns an iterator of fixed-sized chunks of the referencing slice, starting at the end
+    /// of the slice.
+    ///
+    /// It is the fallible version of [into_rchunks()](Chunk::into_rchunks), which returns the chunk back in the
+    /// error if `chunk_size` is zero and slice length is not zero.
+    pub fn try_into_rchunks(
+        self,
+        chunk_size: usize,
+    ) -> Result<RChunks<'a, S, T>, ChunkError<Self>> {
+        if !self.is_empty() && chunk_size == 0 {
+            return Err(ChunkError::ZeroChunkSize { chunk: self });
+        }
+
+        let start = self.start;
+        let slice_len = self.len();
+        let Self { owner, raw, .. } = self;
+
+        let count = if chunk_size > 0 {
+            slice_len.div_ceil(chunk_size)
+        } else {
+            0
+        };
+        Ok(RChunks {
+            partition: PartitionCounter::new(count),
+            chunk_size,
+            start,
+            index: start + slice_len,
+            owner,
+            raw,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Returns an iterator of fixed-sized chunks of the referencing slice, starting at the end
+    /// of the slice.
+    ///
+    /// Each chunk has exactly `chunk_size` elements. The leading elements that do not fill
+    /// a whole chunk are left out, and can be retrieved as a chunk by
+    /// [into_remainder()](RChunksExact::into_remainder).
+    ///
+    /// # Panics
+    /// The method panics if `chunk_size` is zero and slice length is not zero.
+    pub fn into_rchunks_exact(self, chunk_size: usize) -> RChunksExact<'a, S, T> {
+        match self.try_into_rchunks_exact(chunk_size) {
+            Ok(chunks) => chunks,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Returns an iterator of fixed-sized chunks of the referencing slice, starting at the end
+    /// of the slice.
+    ///
+    /// It is the fallible version of [into_rchunks_exact()](Chunk::into_rchunks_exact), which returns the chunk back in the
+    /// error if `chunk_size` is zero and slice length is not zero.
+    pub fn try_into_rchunks_exact(
+        self,
+        chunk_size: usize,
+    ) -> Result<RChunksExact<'a, S, T>, ChunkError<Self>> {
+        if !self.is_empty() && chunk_size == 0 {
+            return Err(ChunkError::ZeroChunkSize { chunk: self });
+        }
+
+        let rem_start = self.start;
+        let slice_len = self.len();
+        let Self { owner, raw, .. } = self;
+
+        let rem_len = if chunk_size > 0 {
+            slice_len % chunk_size
+        } else {
+            0
+        };
+        let start = rem_start + rem_len;
+
+        let count = slice_len.checked_div(chunk_size).unwrap_or(0);
+        Ok(RChunksExact {
+            partition: PartitionCounter::new(count),
+            chunk_size,
+            rem_start,
+            start,
+            index: rem_start + slice_len,
+            owner,
+            raw,
+            _phantom: PhantomData,
+        })
     }
 
     /// Returns an iterator of evenly sized chunks of the referencing slice.
@@ -104,36 +380,192 @@ where
     /// # Panics
     /// The method panics if `num_chunks` is zero.
     pub fn into_even_chunks(self, num_chunks: usize) -> EvenChunks<'a, S, T> {
-        assert!(mem::size_of::<T>() > 0, "zero-sized type is not allowed");
+        match self.try_into_even_chunks(num_chunks) {
+            Ok(chunks) => chunks,
+            Err(err) => panic!("{}", err),
+        }
+    }
 
-        unsafe {
-            let owner = self.owner;
-            let owner_ptr = Arc::as_ptr(&owner);
-            let owner_slice = owner_ptr.as_ref().unwrap().as_ref();
+    /// Returns an iterator of evenly sized chunks of the referencing slice.
+    ///
+    /// It is the fallible version of [into_even_chunks()](Chunk::into_even_chunks), which
+    /// returns the chunk back in the error if `num_chunks` is zero.
+    pub fn try_into_even_chunks(
+        self,
+        num_chunks: usize,
+    ) -> Result<EvenChunks<'a, S, T>, ChunkError<Self>> {
+        if num_chunks == 0 {
+            return Err(ChunkError::ZeroNumChunks { chunk: self });
+        }
 
-            let slice_len = self.slice.as_ref().len();
-            let slice_ptr = self.slice.as_ref().as_ptr();
-            let start = slice_ptr.offset_from(owner_slice.as_ptr()) as usize;
+        let start = self.start;
+        let slice_len = self.len();
+        let Self { owner, raw, .. } = self;
 
-            assert!(num_chunks > 0, "num_chunks must be positive, but get zero");
+        let base_chunk_size = slice_len / num_chunks;
+        let long_end = start + (slice_len % num_chunks) * (base_chunk_size + 1);
 
-            let base_chunk_size = slice_len / num_chunks;
-            let long_end = start + (slice_len % num_chunks) * (base_chunk_size + 1);
+        let count = cmp::min(slice_len, num_chunks);
+        Ok(EvenChunks {
+            partition: PartitionCounter::new(count),
+            index: start,
+            base_chunk_size,
+            long_end,
+            short_end: start + slice_len,
+            owner,
+            raw,
+            _phantom: PhantomData,
+        })
+    }
 
-            EvenChunks {
-                index: start,
-                base_chunk_size,
-                long_end,
-                short_end: start + slice_len,
-                owner,
-                _phantom: PhantomData,
-            }
+    /// Returns an iterator of chunks separated by elements that match `pred`.
+    /// The matched elements are not contained in the chunks.
+    pub fn into_split<P>(self, pred: P) -> Split<'a, S, T, P>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        let start = self.start;
+        let slice_len = self.len();
+        let Self { owner, raw, .. } = self;
+
+        Split {
+            index: start,
+            end: start + slice_len,
+            finished: false,
+            pred,
+            owner,
+            raw,
+            _phantom: PhantomData,
         }
     }
 
+    /// Returns an iterator of at most `n` chunks separated by elements that match `pred`.
+    /// The matched elements are not contained in the chunks.
+    ///
+    /// The last chunk contains the remaining elements of the slice.
+    pub fn into_splitn<P>(self, n: usize, pred: P) -> SplitN<'a, S, T, P>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        let start = self.start;
+        let slice_len = self.len();
+        let Self { owner, raw, .. } = self;
+
+        SplitN {
+            index: start,
+            end: start + slice_len,
+            count: n,
+            pred,
+            owner,
+            raw,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns an iterator of chunks separated by elements that match `pred`.
+    /// The matched element is contained in the end of the preceding chunk.
+    pub fn into_split_inclusive<P>(self, pred: P) -> SplitInclusive<'a, S, T, P>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        let start = self.start;
+        let slice_len = self.len();
+        let Self { owner, raw, .. } = self;
+
+        SplitInclusive {
+            index: start,
+            end: start + slice_len,
+            pred,
+            owner,
+            raw,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns an iterator of chunks of maximal runs of elements, where `pred` holds for each
+    /// consecutive pair of elements in the same chunk.
+    pub fn into_chunk_by<P>(self, pred: P) -> ChunkBy<'a, S, T, P>
+    where
+        P: FnMut(&T, &T) -> bool,
+    {
+        let start = self.start;
+        let slice_len = self.len();
+        let Self { owner, raw, .. } = self;
+
+        ChunkBy {
+            index: start,
+            end: start + slice_len,
+            pred,
+            owner,
+            raw,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Divides the chunk into at most `num_chunks` mostly evenly sized chunks, each of which
+    /// consists of whole groups as defined in [into_chunk_by()](Chunk::into_chunk_by).
+    ///
+    /// The slice is first divided evenly, and then each boundary is moved forward to the
+    /// nearest group edge. The boundaries of very large slices are searched on separate
+    /// threads if the `std` feature is enabled. Empty chunks are left out.
+    ///
+    /// # Panics
+    /// The method panics if `num_chunks` is zero.
+    pub fn into_even_chunks_by<P>(self, num_chunks: usize, pred: P) -> Vec<Chunk<'a, S, T>>
+    where
+        P: Fn(&T, &T) -> bool + Sync,
+    {
+        let start = self.start;
+        let boundaries = utils::group_boundaries(&self, num_chunks, &pred);
+        let Self { owner, raw, .. } = self;
+        let mut counter = PartitionCounter::new(boundaries.len().saturating_sub(1));
+
+        boundaries
+            .iter()
+            .zip(boundaries.iter().skip(1))
+            .map(|(&lhs, &rhs)| {
+                let mut chunk =
+                    Chunk::from_owner_range(owner.clone(), raw, start + lhs, start + rhs);
+                chunk.partition = Some(counter.next_partition());
+                chunk
+            })
+            .collect()
+    }
+
+    /// Gets the partition descriptor if the chunk is produced by a partitioner.
+    ///
+    /// Chunks concatenated from consecutive parts of the same partition retain a
+    /// descriptor spanning these parts. Chunks created otherwise have no descriptor.
+    pub fn partition(&self) -> Option<Partition> {
+        self.partition
+    }
+
     /// Gets the reference count on the owning data.
     pub fn ref_count(&self) -> usize {
-        Arc::strong_count(&self.owner)
+        self.owner.ref_count()
+    }
+
+    /// Lists the chunks referring to the owning data, which are still alive.
+    ///
+    /// Chunk iterators and owned elements referring to the data are not listed.
+    #[cfg(feature = "tracking")]
+    pub fn live_chunks(&self) -> Vec<ChunkInfo> {
+        self.owner.live_chunks()
+    }
+
+    /// Cancels the jobs over the owning data.
+    ///
+    /// The cancellation is shared by all chunks and chunk iterators referring to the data,
+    /// and is observed by [is_cancelled()](Chunk::is_cancelled). Chunk iterators made
+    /// `cancellable()` stop yielding chunks once cancelled. Chunks created anew from the data,
+    /// e.g. after it is recovered, start out not cancelled.
+    pub fn cancel(&self) {
+        self.owner.cancel()
+    }
+
+    /// Checks if the jobs over the owning data are cancelled by any chunk.
+    pub fn is_cancelled(&self) -> bool {
+        self.owner.is_cancelled()
     }
 
     /// Concatenates contiguous chunks into one chunk.
@@ -145,164 +577,338 @@ where
     where
         I: IntoIterator<Item = Self>,
     {
-        unsafe {
-            let mut chunks = chunks.into_iter();
-
-            // obtain inner pointer from the first chunk
-            let first = chunks.next().expect("the chunks must be non-empty");
-            let owner = first.owner.clone();
-
-            let chunks: Vec<_> = iter::once(first)
-                .chain(chunks.inspect(|chunk| {
-                    // verify if all chunks points to the same owner
-                    assert_eq!(
-                        Arc::as_ptr(&chunk.owner),
-                        Arc::as_ptr(&owner),
-                        "inconsistent owner of the chunks"
-                    );
-                }))
-                .collect();
-
-            // verify if chunks are contiguous
-            chunks
-                .iter()
-                .zip(chunks.iter().skip(1))
-                .for_each(|(prev, next)| {
-                    let prev_end = prev.slice.as_ref().as_ptr_range().end;
-                    let next_start = next.slice.as_ref().as_ptr_range().start;
-                    assert!(prev_end == next_start, "the chunks are not contiguous");
-                });
+        match Self::try_cat(chunks) {
+            Ok(chunk) => chunk,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Concatenates contiguous chunks into one chunk.
+    ///
+    /// It is the fallible version of [cat()](Chunk::cat), which returns the chunks back in the
+    /// error if the chunks are empty, not contiguous, or belong to different owners.
+    pub fn try_cat<I>(chunks: I) -> Result<Self, ChunkError<Vec<Self>>>
+    where
+        I: IntoIterator<Item = Self>,
+    {
+        let chunks: Vec<_> = chunks.into_iter().collect();
+
+        // obtain inner pointer from the first chunk
+        let (owner, raw) = match chunks.first() {
+            Some(first) => (first.owner.clone(), first.raw),
+            None => return Err(ChunkError::EmptyChunks),
+        };
+
+        // verify if all chunks points to the same owner
+        let inconsistent = chunks.iter().position(|chunk| !chunk.owner.ptr_eq(&owner));
+        if let Some(index) = inconsistent {
+            return Err(ChunkError::InconsistentOwner { index, chunks });
+        }
+
+        // verify if chunks are contiguous
+        let discontiguous = chunks
+            .iter()
+            .zip(chunks.iter().skip(1))
+            .position(|(prev, next)| prev.end != next.start);
+        if let Some(index) = discontiguous {
+            return Err(ChunkError::NotContiguous {
+                index: index + 1,
+                chunks,
+            });
+        }
 
-            // save slice range
-            let len = chunks.iter().map(|chunk| chunk.slice.as_ref().len()).sum();
-            let slice_ptr: *const T = chunks.first().unwrap().as_ref().as_ptr();
+        // save slice range
+        let start = chunks.first().unwrap().start;
+        let end = chunks.last().unwrap().end;
+        let partition = Partition::merge(chunks.iter().map(|chunk| chunk.partition));
 
-            // free chunk references
-            drop(chunks);
+        // free chunk references
+        drop(chunks);
 
-            // create returning chunk
-            let slice = {
-                let slice = slice::from_raw_parts(slice_ptr, len);
-                NonNull::new_unchecked(slice as *const [T] as *mut [T])
-            };
+        // create returning chunk
+        let mut chunk = Self::from_owner_range(owner, raw, start, end);
+        chunk.partition = partition;
+        Ok(chunk)
+    }
+
+    /// Concatenates chunks given in arbitrary order into one chunk.
+    ///
+    /// The chunks are sorted by their offsets in the owner before concatenation.
+    ///
+    /// # Panics
+    /// The method panics if the chunks are empty, overlapping, do not cover a
+    /// contiguous range, or belong to different owners.
+    pub fn cat_unordered<I>(chunks: I) -> Self
+    where
+        I: IntoIterator<Item = Self>,
+    {
+        match Self::try_cat_unordered(chunks) {
+            Ok(chunk) => chunk,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Concatenates chunks given in arbitrary order into one chunk.
+    ///
+    /// It is the fallible version of [cat_unordered()](Chunk::cat_unordered). If the chunks
+    /// leave gaps in between, the missing ranges are reported in
+    /// [MissingRanges](ChunkError::MissingRanges) along with the chunks.
+    pub fn try_cat_unordered<I>(chunks: I) -> Result<Self, ChunkError<Vec<Self>>>
+    where
+        I: IntoIterator<Item = Self>,
+    {
+        let mut chunks: Vec<_> = chunks.into_iter().collect();
+
+        // verify if all chunks points to the same owner
+        let owner = match chunks.first() {
+            Some(first) => first.owner.clone(),
+            None => return Err(ChunkError::EmptyChunks),
+        };
+        let inconsistent = chunks.iter().position(|chunk| !chunk.owner.ptr_eq(&owner));
+        if let Some(index) = inconsistent {
+            return Err(ChunkError::InconsistentOwner { index, chunks });
+        }
+        drop(owner);
 
-            Chunk {
-                owner,
-                slice,
-                _phantom: PhantomData,
+        match utils::sort_contiguous(&mut chunks, None, Self::range) {
+            Ok(()) => Self::try_cat(chunks),
+            Err(Discontinuity::Overlapping(index)) => {
+                Err(ChunkError::Overlapping { index, chunks })
+            }
+            Err(Discontinuity::Missing(ranges)) => {
+                Err(ChunkError::MissingRanges { ranges, chunks })
             }
         }
     }
 
     pub fn to_range<R>(&self, range: R) -> Option<Self>
     where
-        R: RangeBounds<usize> + SliceIndex<[T], Output = [T]>,
+        R: RangeBounds<usize>,
     {
-        unsafe {
-            let Self { owner, slice, .. } = self;
-            let slice: &mut [T] = slice.clone().as_mut();
-            let new_slice: &mut [T] = slice.get_mut(range)?;
-            let new_slice_ptr = NonNull::new_unchecked(new_slice);
-
-            Some(Self {
-                owner: owner.clone(),
-                slice: new_slice_ptr,
-                _phantom: PhantomData,
-            })
-        }
+        let (start, end) = utils::resolve_range(range, self.len())?;
+        Some(Self::from_owner_range(
+            self.owner.clone(),
+            self.raw,
+            self.start + start,
+            self.start + end,
+        ))
     }
 
     pub fn into_range<R>(self, range: R) -> Option<Self>
     where
-        R: RangeBounds<usize> + SliceIndex<[T], Output = [T]>,
+        R: RangeBounds<usize>,
     {
         self.to_range(range)
     }
 
     /// Returns an iterator of owned references to each element of the slice.
     pub fn into_iter_owned(self) -> Iter<'a, S, T> {
-        unsafe {
-            let index = self.start_index();
-            let Self { owner, slice, .. } = self;
-            let end = index + slice.as_ref().len();
+        let Self {
+            owner,
+            raw,
+            start,
+            end,
+            ..
+        } = self;
 
-            Iter {
-                owner,
-                index,
-                end,
-                _phantom: PhantomData,
-            }
+        Iter {
+            owner,
+            raw,
+            index: start,
+            end,
+            _phantom: PhantomData,
         }
     }
 
     /// Returns an iterator of owned references to each element of the slice.
     pub fn into_windows_owned(self, window_size: usize) -> Windows<'a, S, T> {
-        unsafe {
-            let index = self.start_index();
-            let slice_len = self.slice.as_ref().len();
+        let Self {
+            owner,
+            raw,
+            start,
+            end,
+            ..
+        } = self;
 
-            Windows {
-                owner: self.owner,
-                size: window_size,
-                index,
-                end: index + slice_len,
-                _phantom: PhantomData,
-            }
+        Windows {
+            owner,
+            raw,
+            size: window_size,
+            index: start,
+            end,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Creates a weak handle on the chunk, which does not keep the owner alive.
+    ///
+    /// The handle does not prevent [try_unwrap_owner()](Chunk::try_unwrap_owner) from
+    /// recovering the data. It can be upgraded back to the chunk as long as the data is alive.
+    pub fn downgrade(&self) -> WeakChunk<'a, S, T> {
+        WeakChunk {
+            owner: self.owner.downgrade(),
+            raw: self.raw,
+            start: self.start,
+            end: self.end,
+            partition: self.partition,
+            _phantom: PhantomData,
         }
     }
 
     pub fn into_arc_owner(self) -> Arc<S> {
-        self.owner
+        // shared chunks never mutate the data
+        unsafe { self.owner.into_arc() }
     }
 
+    #[cfg(feature = "std")]
     pub fn into_arc_ref(self) -> ArcRef<S, [T]> {
-        unsafe {
-            let Self { owner, slice, .. } = self;
-            ArcRef::new(owner).map(|_| slice.as_ref())
+        let Self {
+            owner,
+            raw,
+            start,
+            end,
+            ..
+        } = self;
+        let owner = unsafe { owner.into_arc() };
+        ArcRef::new(owner).map(|_| unsafe { raw.slice(start, end) })
+    }
+
+    /// Tries to recover the reference-counted owner, which also works for unsized owners
+    /// such as `[T]`.
+    ///
+    /// Unlike [into_arc_owner()](Chunk::into_arc_owner), the method succeeds only if the
+    /// referencing chunk iterator and all other chunks are dropped, so that the returned
+    /// [Arc] is unique. Otherwise, it returns the chunk intact. [Weak chunks](WeakChunk) do
+    /// not prevent the recovery, and can still be upgraded as long as the [Arc] is alive.
+    pub fn try_unwrap_arc(self) -> Result<Arc<S>, Self> {
+        let Self {
+            owner,
+            raw,
+            start,
+            end,
+            partition,
+            tracker,
+            ..
+        } = self;
+
+        owner.try_into_arc().map_err(|owner| Self {
+            owner,
+            raw,
+            start,
+            end,
+            partition,
+            tracker,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Creates a chunk referencing to the `start..end` range of the owner.
+    pub(crate) fn from_owner_range(
+        owner: Owner<S>,
+        raw: RawSlice<T>,
+        start: usize,
+        end: usize,
+    ) -> Self {
+        assert!(start <= end && end <= raw.len());
+
+        Chunk {
+            tracker: Tracker::new(owner.addr(), start..end),
+            owner,
+            raw,
+            start,
+            end,
+            partition: None,
+            _phantom: PhantomData,
         }
     }
 
+    /// Gets the offset of the chunk in the owner.
+    pub fn offset(&self) -> usize {
+        self.start
+    }
+
+    /// Gets the range of the chunk in the owner.
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+
+    /// Gets the length of the owner.
+    pub fn owner_len(&self) -> usize {
+        self.raw.len()
+    }
+
+    /// Returns an iterator of elements along with their indices in the owner.
+    pub fn enumerate_global(&self) -> iter::Zip<RangeFrom<usize>, slice::Iter<'_, T>> {
+        (self.offset()..).zip(self.iter())
+    }
+}
+
+impl<'a, S, T> Chunk<'a, S, T>
+where
+    S: ChunkOwner<T> + Send + Sync + 'a,
+    T: Send + Sync,
+{
     /// Tries to recover the owning data.
     ///
     /// The method succeeds if the referencing chunk iterator and all chunks are dropped.
     /// Otherwise, it returns the guard intact.
     pub fn try_unwrap_owner(self) -> Result<S, Self> {
-        let Self { owner, slice, .. } = self;
-        Arc::try_unwrap(owner).map_err(|owner| Self {
+        let Self {
             owner,
-            slice,
+            raw,
+            start,
+            end,
+            partition,
+            tracker,
+            ..
+        } = self;
+        owner.try_unwrap().map_err(|owner| Self {
+            owner,
+            raw,
+            start,
+            end,
+            partition,
+            tracker,
             _phantom: PhantomData,
         })
     }
+}
 
-    fn start_index(&self) -> usize {
-        unsafe {
-            let owner_ptr = Arc::as_ptr(&self.owner);
-            let owner_slice = owner_ptr.as_ref().unwrap().as_ref();
-
-            let slice_ptr = self.slice.as_ref().as_ptr();
-            slice_ptr.offset_from(owner_slice.as_ptr()) as usize
-        }
+impl<'a, S, T> Chunk<'a, OnRelease<S>, T>
+where
+    S: ChunkOwner<T> + Send + Sync + 'a,
+    T: Send + Sync,
+{
+    /// Creates a chunk covering the whole owner, which invokes `on_release` with the owner
+    /// once the last chunk or chunk iterator referring to it is dropped. See [OnRelease].
+    pub fn with_on_release<F>(owner: S, on_release: F) -> Self
+    where
+        F: FnOnce(Result<S, Poisoned<S>>) + Send + 'static,
+    {
+        let state = Arc::<OwnerState>::default();
+        let owner = Arc::new(OnRelease::new(owner, state.clone(), on_release));
+        let raw = RawSlice::from_ref(owner.as_slice());
+        Self::from_owner_range(Owner::with_state(owner, state), raw, 0, raw.len())
     }
 }
 
 unsafe impl<'a, S, T> Send for Chunk<'a, S, T>
 where
-    S: AsRef<[T]> + Send + Sync + 'a,
+    S: ChunkOwner<T> + Send + Sync + ?Sized + 'a,
     T: Send + Sync,
 {
 }
 
 unsafe impl<'a, S, T> Sync for Chunk<'a, S, T>
 where
-    S: AsRef<[T]> + Send + Sync + 'a,
+    S: ChunkOwner<T> + Send + Sync + ?Sized + 'a,
     T: Send + Sync,
 {
 }
 
 impl<'a, S, T> AsRef<[T]> for Chunk<'a, S, T>
 where
-    S: AsRef<[T]> + Send + Sync + 'a,
+    S: ChunkOwner<T> + Send + Sync + ?Sized + 'a,
     T: Send + Sync,
 {
     fn as_ref(&self) -> &[T] {
@@ -312,19 +918,19 @@ where
 
 impl<'a, S, T> Deref for Chunk<'a, S, T>
 where
-    S: AsRef<[T]> + Send + Sync + 'a,
+    S: ChunkOwner<T> + Send + Sync + ?Sized + 'a,
     T: Send + Sync,
 {
     type Target = [T];
 
     fn deref(&self) -> &Self::Target {
-        unsafe { self.slice.as_ref() }
+        unsafe { self.raw.slice(self.start, self.end) }
     }
 }
 
 impl<'a, S, T> IntoIterator for &'a Chunk<'_, S, T>
 where
-    S: AsRef<[T]> + Send + Sync + 'a,
+    S: ChunkOwner<T> + Send + Sync + ?Sized + 'a,
     T: Send + Sync,
 {
     type Item = &'a T;
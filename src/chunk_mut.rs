@@ -1,68 +1,217 @@
-use std::{ops::RangeBounds, slice::SliceIndex};
+use core::{
+    mem::ManuallyDrop,
+    ops::{Range, RangeBounds, RangeFrom},
+    ptr,
+};
 
 use crate::{
-    chunks_mut::{EvenChunksMut, IterMut, SizedChunksMut},
+    array_chunk_mut::ArrayChunkMut,
+    checked::Checker,
+    chunks_mut::{
+        ChunkByMut, EvenChunksMut, ExactChunksMut, IterMut, RChunksExactMut, RChunksMut,
+        SizedChunksMut, SplitInclusiveMut, SplitMut, SplitNMut,
+    },
     common::*,
+    error::{ChunkError, TryUnwrapResult},
+    on_release::OnRelease,
+    owner::{Owner, OwnerState},
+    partition::{Partition, PartitionCounter},
+    poison::Poisoned,
+    raw::RawSlice,
+    tracking::Tracker,
+    utils::{self, Discontinuity},
 };
 
 /// A mutable sub-slice reference-counted reference to a slice-like data.
 #[derive(Debug)]
 pub struct ChunkMut<'a, S, T>
 where
-    S: AsMut<[T]> + Send + Sync + 'a,
+    S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
     T: Send + Sync,
 {
-    pub(super) owner: Arc<S>,
-    pub(super) slice: NonNull<[T]>,
+    // unregistered before the owner is dropped, as its address may be reused by a new owner
+    pub(super) tracker: Tracker,
+    pub(super) checker: Checker,
+    pub(super) owner: Owner<S>,
+    pub(super) raw: RawSlice<T>,
+    pub(super) start: usize,
+    pub(super) end: usize,
+    pub(super) partition: Option<Partition>,
     pub(super) _phantom: PhantomData<&'a S>,
 }
 
+/// The fields moved out of a mutable chunk without running its destructor.
+pub(crate) struct ChunkMutParts<S: ?Sized, T> {
+    pub(crate) owner: Owner<S>,
+    pub(crate) raw: RawSlice<T>,
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+    pub(crate) partition: Option<Partition>,
+    pub(crate) tracker: Tracker,
+    pub(crate) checker: Checker,
+}
+
 impl<'a, S, T> ChunkMut<'a, S, T>
 where
-    S: AsMut<[T]> + Send + Sync + 'a,
+    S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
     T: Send + Sync,
 {
-    pub fn new(owner: S) -> Self {
+    pub fn new(owner: S) -> Self
+    where
+        S: Sized,
+    {
         Self::from_arc(Arc::new(owner))
     }
 
+    /// Creates a chunk covering the whole owner.
+    ///
+    /// # Panics
+    /// The method panics if the owner is shared by other [Arc] or [Weak](std::sync::Weak)
+    /// pointers, which would otherwise observe the data while the chunks mutate it.
     pub fn from_arc(owner: Arc<S>) -> Self {
-        unsafe {
-            let ptr = Arc::as_ptr(&owner) as *mut S;
-            let slice: &mut [T] = ptr.as_mut().unwrap().as_mut();
-            let slice = NonNull::new_unchecked(slice as *mut [T]);
-            Self {
-                owner,
-                slice,
-                _phantom: PhantomData,
-            }
+        match Self::try_from_arc(owner) {
+            Ok(chunk) => chunk,
+            Err(_) => panic!("the owner must not be shared"),
         }
     }
 
+    /// Creates a chunk covering the whole owner.
+    ///
+    /// It is the fallible version of [from_arc()](ChunkMut::from_arc), which returns the
+    /// owner back if it is shared.
+    pub fn try_from_arc(mut owner: Arc<S>) -> Result<Self, Arc<S>> {
+        let raw = match Arc::get_mut(&mut owner) {
+            Some(owner) => RawSlice::from_mut(owner.as_mut_slice()),
+            None => return Err(owner),
+        };
+        Ok(unsafe { Self::from_owner_range(Owner::new(owner), raw, 0, raw.len()) })
+    }
+
     /// Splits the chunk into two sub-chunks, divided at specified index.
     ///
     /// # Panics
     /// The method panics if the index is out of bound.
-    pub fn split_at(mut self, index: usize) -> (ChunkMut<'a, S, T>, ChunkMut<'a, S, T>) {
+    pub fn split_at(self, index: usize) -> (ChunkMut<'a, S, T>, ChunkMut<'a, S, T>) {
+        match self.try_split_at(index) {
+            Ok(pair) => pair,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Splits the chunk into two sub-chunks, divided at specified index.
+    ///
+    /// It returns the chunk back in the error if the index is out of bound.
+    pub fn try_split_at(self, index: usize) -> Result<(Self, Self), ChunkError<Self>> {
+        let len = self.len();
+        if index > len {
+            return Err(ChunkError::OutOfBounds {
+                index,
+                len,
+                chunk: self,
+            });
+        }
+
+        let ChunkMutParts {
+            owner,
+            raw,
+            start,
+            end,
+            checker,
+            ..
+        } = self.into_parts();
+        let mid = start + index;
+
+        // release the range before the halves take it over
+        checker.release();
+
         unsafe {
-            let owner = self.owner;
-            let slice: &mut [T] = self.slice.as_mut();
-            let lslice = NonNull::new_unchecked(&mut slice[0..index] as *mut [T]);
-            let rslice = NonNull::new_unchecked(&mut slice[index..] as *mut [T]);
-
-            (
-                ChunkMut {
-                    owner: owner.clone(),
-                    slice: lslice,
-                    _phantom: PhantomData,
-                },
-                ChunkMut {
-                    owner,
-                    slice: rslice,
-                    _phantom: PhantomData,
-                },
-            )
+            Ok((
+                ChunkMut::from_owner_range(owner.clone(), raw, start, mid),
+                ChunkMut::from_owner_range(owner, raw, mid, end),
+            ))
+        }
+    }
+
+    /// Splits the chunk into sub-chunks, divided at each of the specified indices.
+    ///
+    /// It returns `indices.len() + 1` chunks. The indices must be sorted in ascending order
+    /// and must not exceed the length of the chunk, otherwise the chunk is returned
+    /// back in the error.
+    pub fn split_at_indices(self, indices: &[usize]) -> Result<Vec<Self>, ChunkError<Self>> {
+        let len = self.len();
+        let mut prev = 0;
+
+        for &index in indices {
+            if index > len {
+                return Err(ChunkError::OutOfBounds {
+                    index,
+                    len,
+                    chunk: self,
+                });
+            }
+            if index < prev {
+                return Err(ChunkError::UnsortedIndices {
+                    prev,
+                    next: index,
+                    chunk: self,
+                });
+            }
+            prev = index;
+        }
+
+        let mut chunks = Vec::with_capacity(indices.len() + 1);
+        let mut prev = 0;
+        let rest = indices.iter().fold(self, |rest, &index| {
+            let (lchunk, rchunk) = rest.split_at(index - prev);
+            chunks.push(lchunk);
+            prev = index;
+            rchunk
+        });
+        chunks.push(rest);
+
+        let mut counter = PartitionCounter::new(chunks.len());
+        chunks
+            .iter_mut()
+            .for_each(|chunk| chunk.partition = Some(counter.next_partition()));
+
+        Ok(chunks)
+    }
+
+    /// Splits the chunk into consecutive sub-chunks with specified lengths.
+    ///
+    /// It returns `lengths.len()` chunks. The lengths must sum up to the length of the
+    /// chunk, otherwise the chunk is returned back in the error.
+    pub fn split_by_lengths(self, lengths: &[usize]) -> Result<Vec<Self>, ChunkError<Self>> {
+        let len = self.len();
+        let total = lengths
+            .iter()
+            .try_fold(0usize, |total, &length| total.checked_add(length));
+
+        match total {
+            Some(total) if total == len => {}
+            total => {
+                return Err(ChunkError::LengthMismatch {
+                    total: total.unwrap_or(usize::MAX),
+                    len,
+                    chunk: self,
+                });
+            }
         }
+
+        let mut chunks = Vec::with_capacity(lengths.len());
+        let rest = lengths.iter().fold(self, |rest, &length| {
+            let (lchunk, rchunk) = rest.split_at(length);
+            chunks.push(lchunk);
+            rchunk
+        });
+        debug_assert!(rest.is_empty());
+
+        let mut counter = PartitionCounter::new(chunks.len());
+        chunks
+            .iter_mut()
+            .for_each(|chunk| chunk.partition = Some(counter.next_partition()));
+
+        Ok(chunks)
     }
 
     /// Returns an iterator of fixed-sized chunks of the refencing slice.
@@ -76,28 +225,197 @@ where
     /// # Panics
     /// The method panics if `chunk_size` is zero and slice length is not zero.
     pub fn into_sized_chunks(self, chunk_size: usize) -> SizedChunksMut<'a, S, T> {
-        assert!(mem::size_of::<T>() > 0, "zero-sized type is not allowed");
+        match self.try_into_sized_chunks(chunk_size) {
+            Ok(chunks) => chunks,
+            Err(err) => panic!("{}", err),
+        }
+    }
 
-        unsafe {
-            let start = self.start_index();
-            let owner = self.owner;
-            let slice_len = self.slice.as_ref().len();
-
-            assert!(
-                slice_len == 0 || chunk_size > 0,
-                "chunk_size must be positive for non-empty slice"
-            );
-
-            SizedChunksMut {
-                chunk_size,
-                index: start,
-                end: start + slice_len,
-                owner,
-                _phantom: PhantomData,
-            }
+    /// Returns an iterator of fixed-sized chunks of the refencing slice.
+    ///
+    /// It is the fallible version of [into_sized_chunks()](ChunkMut::into_sized_chunks), which
+    /// returns the chunk back in the error if `chunk_size` is zero and slice length is not
+    /// zero.
+    pub fn try_into_sized_chunks(
+        self,
+        chunk_size: usize,
+    ) -> Result<SizedChunksMut<'a, S, T>, ChunkError<Self>> {
+        if !self.is_empty() && chunk_size == 0 {
+            return Err(ChunkError::ZeroChunkSize { chunk: self });
+        }
+
+        let start = self.start;
+        let slice_len = self.len();
+        let ChunkMutParts { owner, raw, .. } = self.into_parts();
+
+        let count = if chunk_size > 0 {
+            slice_len.div_ceil(chunk_size)
+        } else {
+            0
+        };
+        Ok(SizedChunksMut {
+            partition: PartitionCounter::new(count),
+            chunk_size,
+            index: start,
+            end: start + slice_len,
+            owner,
+            raw,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Returns an iterator of fixed-sized chunks of the referencing slice.
+    ///
+    /// Each chunk has exactly `chunk_size` elements. The trailing elements that do not fill
+    /// a whole chunk are left out, and can be retrieved as a chunk by
+    /// [into_remainder()](ExactChunksMut::into_remainder).
+    ///
+    /// # Panics
+    /// The method panics if `chunk_size` is zero and slice length is not zero.
+    pub fn into_exact_chunks(self, chunk_size: usize) -> ExactChunksMut<'a, S, T> {
+        match self.try_into_exact_chunks(chunk_size) {
+            Ok(chunks) => chunks,
+            Err(err) => panic!("{}", err),
         }
     }
 
+    /// Returns an iterator of fixed-sized chunks of the referencing slice.
+    ///
+    /// It is the fallible version of [into_exact_chunks()](ChunkMut::into_exact_chunks), which returns the chunk back in the
+    /// error if `chunk_size` is zero and slice length is not zero.
+    pub fn try_into_exact_chunks(
+        self,
+        chunk_size: usize,
+    ) -> Result<ExactChunksMut<'a, S, T>, ChunkError<Self>> {
+        if !self.is_empty() && chunk_size == 0 {
+            return Err(ChunkError::ZeroChunkSize { chunk: self });
+        }
+
+        let start = self.start;
+        let slice_len = self.len();
+        let ChunkMutParts { owner, raw, .. } = self.into_parts();
+
+        let rem_len = if chunk_size > 0 {
+            slice_len % chunk_size
+        } else {
+            0
+        };
+        let end = start + slice_len - rem_len;
+
+        let count = slice_len.checked_div(chunk_size).unwrap_or(0);
+        Ok(ExactChunksMut {
+            partition: PartitionCounter::new(count),
+            chunk_size,
+            index: start,
+            end,
+            rem_end: end + rem_len,
+            owner,
+            raw,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Returns an iterator of fixed-sized chunks of the referencing slice, starting at the end
+    /// of the slice.
+    ///
+    /// Each chunk has `chunk_size` elements, expect the last chunk, which covers the beginning
+    /// of the slice, maybe shorter if there aren't enough elements.
+    ///
+    /// # Panics
+    /// The method panics if `chunk_size` is zero and slice length is not zero.
+    pub fn into_rchunks(self, chunk_size: usize) -> RChunksMut<'a, S, T> {
+        match self.try_into_rchunks(chunk_size) {
+            Ok(chunks) => chunks,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Returns an iterator of fixed-sized chunks of the referencing slice, starting at the end
+    /// of the slice.
+    ///
+    /// It is the fallible version of [into_rchunks()](ChunkMut::into_rchunks), which returns the chunk back in the
+    /// error if `chunk_size` is zero and slice length is not zero.
+    pub fn try_into_rchunks(
+        self,
+        chunk_size: usize,
+    ) -> Result<RChunksMut<'a, S, T>, ChunkError<Self>> {
+        if !self.is_empty() && chunk_size == 0 {
+            return Err(ChunkError::ZeroChunkSize { chunk: self });
+        }
+
+        let start = self.start;
+        let slice_len = self.len();
+        let ChunkMutParts { owner, raw, .. } = self.into_parts();
+
+        let count = if chunk_size > 0 {
+            slice_len.div_ceil(chunk_size)
+        } else {
+            0
+        };
+        Ok(RChunksMut {
+            partition: PartitionCounter::new(count),
+            chunk_size,
+            start,
+            index: start + slice_len,
+            owner,
+            raw,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Returns an iterator of fixed-sized chunks of the referencing slice, starting at the end
+    /// of the slice.
+    ///
+    /// Each chunk has exactly `chunk_size` elements. The leading elements that do not fill
+    /// a whole chunk are left out, and can be retrieved as a chunk by
+    /// [into_remainder()](RChunksExactMut::into_remainder).
+    ///
+    /// # Panics
+    /// The method panics if `chunk_size` is zero and slice length is not zero.
+    pub fn into_rchunks_exact(self, chunk_size: usize) -> RChunksExactMut<'a, S, T> {
+        match self.try_into_rchunks_exact(chunk_size) {
+            Ok(chunks) => chunks,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Returns an iterator of fixed-sized chunks of the referencing slice, starting at the end
+    /// of the slice.
+    ///
+    /// It is the fallible version of [into_rchunks_exact()](ChunkMut::into_rchunks_exact), which returns the chunk back in the
+    /// error if `chunk_size` is zero and slice length is not zero.
+    pub fn try_into_rchunks_exact(
+        self,
+        chunk_size: usize,
+    ) -> Result<RChunksExactMut<'a, S, T>, ChunkError<Self>> {
+        if !self.is_empty() && chunk_size == 0 {
+            return Err(ChunkError::ZeroChunkSize { chunk: self });
+        }
+
+        let rem_start = self.start;
+        let slice_len = self.len();
+        let ChunkMutParts { owner, raw, .. } = self.into_parts();
+
+        let rem_len = if chunk_size > 0 {
+            slice_len % chunk_size
+        } else {
+            0
+        };
+        let start = rem_start + rem_len;
+
+        let count = slice_len.checked_div(chunk_size).unwrap_or(0);
+        Ok(RChunksExactMut {
+            partition: PartitionCounter::new(count),
+            chunk_size,
+            rem_start,
+            start,
+            index: rem_start + slice_len,
+            owner,
+            raw,
+            _phantom: PhantomData,
+        })
+    }
+
     /// Returns an iterator of evenly sized chunks of the referencing slice.
     ///
     /// It returns exactly `num_chunks` mostly evenly sized chunks.
@@ -105,32 +423,238 @@ where
     /// # Panics
     /// The method panics if `num_chunks` is zero.
     pub fn into_even_chunks(self, num_chunks: usize) -> EvenChunksMut<'a, S, T> {
-        assert!(mem::size_of::<T>() > 0, "zero-sized type is not allowed");
+        match self.try_into_even_chunks(num_chunks) {
+            Ok(chunks) => chunks,
+            Err(err) => panic!("{}", err),
+        }
+    }
 
-        unsafe {
-            let start = self.start_index();
-            let owner = self.owner;
-            let slice_len = self.slice.as_ref().len();
+    /// Returns an iterator of evenly sized chunks of the referencing slice.
+    ///
+    /// It is the fallible version of [into_even_chunks()](ChunkMut::into_even_chunks), which
+    /// returns the chunk back in the error if `num_chunks` is zero.
+    pub fn try_into_even_chunks(
+        self,
+        num_chunks: usize,
+    ) -> Result<EvenChunksMut<'a, S, T>, ChunkError<Self>> {
+        if num_chunks == 0 {
+            return Err(ChunkError::ZeroNumChunks { chunk: self });
+        }
+
+        let start = self.start;
+        let slice_len = self.len();
+        let ChunkMutParts { owner, raw, .. } = self.into_parts();
+
+        let base_chunk_size = slice_len / num_chunks;
+        let long_end = start + (slice_len % num_chunks) * (base_chunk_size + 1);
+
+        let count = cmp::min(slice_len, num_chunks);
+        Ok(EvenChunksMut {
+            partition: PartitionCounter::new(count),
+            index: start,
+            base_chunk_size,
+            long_end,
+            short_end: start + slice_len,
+            owner,
+            raw,
+            _phantom: PhantomData,
+        })
+    }
 
-            assert!(num_chunks > 0, "num_chunks must be positive, but get zero");
+    /// Splits the chunk into a chunk of `N`-element arrays and the remaining elements.
+    ///
+    /// The array chunk covers the longest prefix whose length is a multiple of `N`,
+    /// and the returned remainder has less than `N` elements.
+    ///
+    /// # Panics
+    /// The method panics if `N` is zero.
+    pub fn into_array_chunks<const N: usize>(
+        self,
+    ) -> (ArrayChunkMut<'a, S, T, N>, ChunkMut<'a, S, T>) {
+        assert!(N > 0, "array size must be positive, but get zero");
+
+        let num_arrays = self.len() / N;
+        let (lchunk, rchunk) = self.split_at(num_arrays * N);
 
-            let base_chunk_size = slice_len / num_chunks;
-            let long_end = start + (slice_len % num_chunks) * (base_chunk_size + 1);
+        let ChunkMutParts {
+            owner,
+            raw,
+            start,
+            end,
+            tracker,
+            checker,
+            ..
+        } = lchunk.into_parts();
 
-            EvenChunksMut {
-                index: start,
-                base_chunk_size,
-                long_end,
-                short_end: start + slice_len,
+        (
+            ArrayChunkMut {
                 owner,
+                raw,
+                start,
+                end,
+                tracker,
+                checker,
                 _phantom: PhantomData,
-            }
+            },
+            rchunk,
+        )
+    }
+
+    /// Returns an iterator of chunks separated by elements that match `pred`.
+    /// The matched elements are not contained in the chunks.
+    pub fn into_split<P>(self, pred: P) -> SplitMut<'a, S, T, P>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        let start = self.start;
+        let slice_len = self.len();
+        let ChunkMutParts { owner, raw, .. } = self.into_parts();
+
+        SplitMut {
+            index: start,
+            end: start + slice_len,
+            finished: false,
+            pred,
+            owner,
+            raw,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns an iterator of at most `n` chunks separated by elements that match `pred`.
+    /// The matched elements are not contained in the chunks.
+    ///
+    /// The last chunk contains the remaining elements of the slice.
+    pub fn into_splitn<P>(self, n: usize, pred: P) -> SplitNMut<'a, S, T, P>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        let start = self.start;
+        let slice_len = self.len();
+        let ChunkMutParts { owner, raw, .. } = self.into_parts();
+
+        SplitNMut {
+            index: start,
+            end: start + slice_len,
+            count: n,
+            pred,
+            owner,
+            raw,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns an iterator of chunks separated by elements that match `pred`.
+    /// The matched element is contained in the end of the preceding chunk.
+    pub fn into_split_inclusive<P>(self, pred: P) -> SplitInclusiveMut<'a, S, T, P>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        let start = self.start;
+        let slice_len = self.len();
+        let ChunkMutParts { owner, raw, .. } = self.into_parts();
+
+        SplitInclusiveMut {
+            index: start,
+            end: start + slice_len,
+            pred,
+            owner,
+            raw,
+            _phantom: PhantomData,
         }
     }
 
+    /// Returns an iterator of chunks of maximal runs of elements, where `pred` holds for each
+    /// consecutive pair of elements in the same chunk.
+    pub fn into_chunk_by<P>(self, pred: P) -> ChunkByMut<'a, S, T, P>
+    where
+        P: FnMut(&T, &T) -> bool,
+    {
+        let start = self.start;
+        let slice_len = self.len();
+        let ChunkMutParts { owner, raw, .. } = self.into_parts();
+
+        ChunkByMut {
+            index: start,
+            end: start + slice_len,
+            pred,
+            owner,
+            raw,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Divides the chunk into at most `num_chunks` mostly evenly sized chunks, each of which
+    /// consists of whole groups as defined in [into_chunk_by()](ChunkMut::into_chunk_by).
+    ///
+    /// The slice is first divided evenly, and then each boundary is moved forward to the
+    /// nearest group edge. The boundaries of very large slices are searched on separate
+    /// threads if the `std` feature is enabled. Empty chunks are left out.
+    ///
+    /// # Panics
+    /// The method panics if `num_chunks` is zero.
+    pub fn into_even_chunks_by<P>(self, num_chunks: usize, pred: P) -> Vec<ChunkMut<'a, S, T>>
+    where
+        P: Fn(&T, &T) -> bool + Sync,
+    {
+        let start = self.start;
+        let boundaries = utils::group_boundaries(&self, num_chunks, &pred);
+        let ChunkMutParts {
+            owner,
+            raw,
+            checker,
+            ..
+        } = self.into_parts();
+        checker.release();
+        let mut counter = PartitionCounter::new(boundaries.len().saturating_sub(1));
+
+        boundaries
+            .iter()
+            .zip(boundaries.iter().skip(1))
+            .map(|(&lhs, &rhs)| {
+                let mut chunk = unsafe {
+                    ChunkMut::from_owner_range(owner.clone(), raw, start + lhs, start + rhs)
+                };
+                chunk.partition = Some(counter.next_partition());
+                chunk
+            })
+            .collect()
+    }
+
+    /// Gets the partition descriptor if the chunk is produced by a partitioner.
+    ///
+    /// Chunks concatenated from consecutive parts of the same partition retain a
+    /// descriptor spanning these parts. Chunks created otherwise have no descriptor.
+    pub fn partition(&self) -> Option<Partition> {
+        self.partition
+    }
+
     /// Gets the reference count on the owning data.
     pub fn ref_count(&self) -> usize {
-        Arc::strong_count(&self.owner)
+        self.owner.ref_count()
+    }
+
+    /// Lists the chunks referring to the owning data, which are still alive.
+    ///
+    /// Chunk iterators and owned elements referring to the data are not listed.
+    #[cfg(feature = "tracking")]
+    pub fn live_chunks(&self) -> Vec<ChunkInfo> {
+        self.owner.live_chunks()
+    }
+
+    /// Cancels the jobs over the owning data.
+    ///
+    /// The cancellation is shared by all chunks and chunk iterators referring to the data,
+    /// and is observed by [is_cancelled()](ChunkMut::is_cancelled). Chunk iterators made
+    /// `cancellable()` stop yielding chunks once cancelled. Chunks created anew from the data,
+    /// e.g. after it is recovered, start out not cancelled.
+    pub fn cancel(&self) {
+        self.owner.cancel()
+    }
+
+    /// Checks if the jobs over the owning data are cancelled by any chunk.
+    pub fn is_cancelled(&self) -> bool {
+        self.owner.is_cancelled()
     }
 
     /// Concatenates contiguous chunks into one chunk.
@@ -142,141 +666,378 @@ where
     where
         I: IntoIterator<Item = Self>,
     {
-        unsafe {
-            let mut chunks = chunks.into_iter();
-
-            // obtain inner pointer from the first chunk
-            let first = chunks.next().expect("the chunks must be non-empty");
-            let owner = first.owner.clone();
-
-            let mut chunks: Vec<_> = iter::once(first)
-                .chain(chunks.inspect(|chunk| {
-                    // verify if all chunks points to the same owner
-                    assert_eq!(
-                        Arc::as_ptr(&chunk.owner),
-                        Arc::as_ptr(&owner),
-                        "inconsistent owner of the chunks"
-                    );
-                }))
-                .collect();
-
-            // verify if chunks are contiguous
-            chunks
-                .iter()
-                .zip(chunks.iter().skip(1))
-                .for_each(|(prev, next)| {
-                    let prev_end = prev.slice.as_ref().as_ptr_range().end;
-                    let next_start = next.slice.as_ref().as_ptr_range().start;
-                    assert!(prev_end == next_start, "the chunks are not contiguous");
-                });
+        match Self::try_cat(chunks) {
+            Ok(chunk) => chunk,
+            Err(err) => panic!("{}", err),
+        }
+    }
 
-            // save slice range
-            let len = chunks.iter().map(|chunk| chunk.slice.as_ref().len()).sum();
-            let slice_ptr: *mut T = chunks.first_mut().unwrap().as_mut().as_mut_ptr();
+    /// Concatenates contiguous chunks into one chunk.
+    ///
+    /// It is the fallible version of [cat()](ChunkMut::cat), which returns the chunks back in the
+    /// error if the chunks are empty, not contiguous, or belong to different owners.
+    pub fn try_cat<I>(chunks: I) -> Result<Self, ChunkError<Vec<Self>>>
+    where
+        I: IntoIterator<Item = Self>,
+    {
+        let chunks: Vec<_> = chunks.into_iter().collect();
 
-            // free chunk references
-            drop(chunks);
+        // obtain inner pointer from the first chunk
+        let (owner, raw) = match chunks.first() {
+            Some(first) => (first.owner.clone(), first.raw),
+            None => return Err(ChunkError::EmptyChunks),
+        };
 
-            // create returning chunk
-            let slice = {
-                let slice = slice::from_raw_parts_mut(slice_ptr, len);
-                NonNull::new_unchecked(slice as *mut [T])
-            };
+        // verify if all chunks points to the same owner
+        let inconsistent = chunks.iter().position(|chunk| !chunk.owner.ptr_eq(&owner));
+        if let Some(index) = inconsistent {
+            return Err(ChunkError::InconsistentOwner { index, chunks });
+        }
 
-            ChunkMut {
-                owner,
-                slice,
-                _phantom: PhantomData,
+        // verify if chunks are contiguous
+        let discontiguous = chunks
+            .iter()
+            .zip(chunks.iter().skip(1))
+            .position(|(prev, next)| prev.end != next.start);
+        if let Some(index) = discontiguous {
+            return Err(ChunkError::NotContiguous {
+                index: index + 1,
+                chunks,
+            });
+        }
+
+        // save slice range
+        let start = chunks.first().unwrap().start;
+        let end = chunks.last().unwrap().end;
+        let partition = Partition::merge(chunks.iter().map(|chunk| chunk.partition));
+
+        // free chunk references
+        drop(chunks);
+
+        // create returning chunk
+        let mut chunk = unsafe { Self::from_owner_range(owner, raw, start, end) };
+        chunk.partition = partition;
+        Ok(chunk)
+    }
+
+    /// Concatenates chunks given in arbitrary order into one chunk.
+    ///
+    /// The chunks are sorted by their offsets in the owner before concatenation.
+    ///
+    /// # Panics
+    /// The method panics if the chunks are empty, overlapping, do not cover a
+    /// contiguous range, or belong to different owners.
+    pub fn cat_unordered<I>(chunks: I) -> Self
+    where
+        I: IntoIterator<Item = Self>,
+    {
+        match Self::try_cat_unordered(chunks) {
+            Ok(chunk) => chunk,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Concatenates chunks given in arbitrary order into one chunk.
+    ///
+    /// It is the fallible version of [cat_unordered()](ChunkMut::cat_unordered). If the chunks
+    /// leave gaps in between, the missing ranges are reported in
+    /// [MissingRanges](ChunkError::MissingRanges) along with the chunks.
+    pub fn try_cat_unordered<I>(chunks: I) -> Result<Self, ChunkError<Vec<Self>>>
+    where
+        I: IntoIterator<Item = Self>,
+    {
+        Self::cat_sorted(chunks.into_iter().collect(), false)
+    }
+
+    /// Takes back all chunks of an owner, and returns a chunk covering the whole owner
+    /// for a new partition.
+    ///
+    /// The chunks can be given in arbitrary order. The returned chunk shares the same
+    /// reference-counted owner with the input chunks, which can be further divided by
+    /// [into_even_chunks()](ChunkMut::into_even_chunks),
+    /// [into_sized_chunks()](ChunkMut::into_sized_chunks),
+    /// [split_by_lengths()](ChunkMut::split_by_lengths) and so on.
+    ///
+    /// It returns the chunks back in the error if the chunks do not cover the whole owner,
+    /// where the missing ranges are reported in [MissingRanges](ChunkError::MissingRanges),
+    /// or the chunks belong to different owners.
+    pub fn repartition<I>(chunks: I) -> Result<Self, ChunkError<Vec<Self>>>
+    where
+        I: IntoIterator<Item = Self>,
+    {
+        Self::cat_sorted(chunks.into_iter().collect(), true)
+    }
+
+    /// Sorts the chunks by offsets and concatenates them. If `full` is true, the chunks must
+    /// cover the whole owner.
+    fn cat_sorted(mut chunks: Vec<Self>, full: bool) -> Result<Self, ChunkError<Vec<Self>>> {
+        // verify if all chunks points to the same owner
+        let owner = match chunks.first() {
+            Some(first) => first.owner.clone(),
+            None => return Err(ChunkError::EmptyChunks),
+        };
+        let inconsistent = chunks.iter().position(|chunk| !chunk.owner.ptr_eq(&owner));
+        if let Some(index) = inconsistent {
+            return Err(ChunkError::InconsistentOwner { index, chunks });
+        }
+        drop(owner);
+
+        let bounds = if full {
+            Some(0..chunks[0].owner_len())
+        } else {
+            None
+        };
+
+        match utils::sort_contiguous(&mut chunks, bounds, Self::range) {
+            Ok(()) => Self::try_cat(chunks),
+            Err(Discontinuity::Overlapping(index)) => {
+                Err(ChunkError::Overlapping { index, chunks })
+            }
+            Err(Discontinuity::Missing(ranges)) => {
+                Err(ChunkError::MissingRanges { ranges, chunks })
             }
         }
     }
 
     pub fn into_range<R>(self, range: R) -> Option<Self>
     where
-        R: RangeBounds<usize> + SliceIndex<[T], Output = [T]>,
+        R: RangeBounds<usize>,
     {
+        let (start, end) = utils::resolve_range(range, self.len())?;
+        let offset = self.start;
+        let ChunkMutParts {
+            owner,
+            raw,
+            checker,
+            ..
+        } = self.into_parts();
+        checker.release();
         unsafe {
-            let Self { owner, slice, .. } = self;
-            let slice: &mut [T] = slice.clone().as_mut();
-            let new_slice: &mut [T] = slice.get_mut(range)?;
-            let new_slice_ptr = NonNull::new_unchecked(new_slice);
+            Some(Self::from_owner_range(
+                owner,
+                raw,
+                offset + start,
+                offset + end,
+            ))
+        }
+    }
 
-            Some(Self {
+    /// Converts to the reference-counted owner.
+    ///
+    /// The method succeeds if no other chunk or chunk iterator refers to the owner, which
+    /// could otherwise mutate the data behind the returned [Arc]. Otherwise, it returns
+    /// the chunk intact. The owner is returned in [TryUnwrapError::Poisoned] if any chunk
+    /// of it was dropped during panics.
+    ///
+    /// [TryUnwrapError::Poisoned]: crate::TryUnwrapError::Poisoned
+    pub fn into_arc_owner(self) -> TryUnwrapResult<Arc<S>, Self> {
+        let ChunkMutParts {
+            owner,
+            raw,
+            start,
+            end,
+            partition,
+            tracker,
+            checker,
+            ..
+        } = self.into_parts();
+        owner.try_recover_arc().map_err(|err| {
+            err.map_shared(|owner| Self {
                 owner,
-                slice: new_slice_ptr,
+                raw,
+                start,
+                end,
+                partition,
+                tracker,
+                checker,
                 _phantom: PhantomData,
             })
-        }
+        })
     }
 
-    pub fn into_arc_owner(self) -> Arc<S> {
-        self.owner
+    /// Converts to a reference to the slice along with the reference-counted owner.
+    ///
+    /// Like [into_arc_owner()](ChunkMut::into_arc_owner), the method succeeds if no other
+    /// chunk or chunk iterator refers to the owner. Otherwise, it returns the chunk intact.
+    #[cfg(feature = "std")]
+    pub fn into_arc_ref(self) -> TryUnwrapResult<ArcRef<S, [T]>, Self> {
+        let (raw, start, end) = (self.raw, self.start, self.end);
+        let to_ref = |owner: Arc<S>| ArcRef::new(owner).map(|_| unsafe { raw.slice(start, end) });
+        self.into_arc_owner()
+            .map(to_ref)
+            .map_err(|err| err.map_poisoned(to_ref))
     }
 
-    pub fn into_arc_ref(self) -> ArcRef<S, [T]> {
-        unsafe {
-            let Self { owner, slice, .. } = self;
-            ArcRef::new(owner).map(|_| slice.as_ref())
+    /// Returns an iterator of owned references to each element of the slice.
+    pub fn into_iter_owned(self) -> IterMut<'a, S, T> {
+        let ChunkMutParts {
+            owner,
+            raw,
+            start,
+            end,
+            ..
+        } = self.into_parts();
+
+        IterMut {
+            owner,
+            raw,
+            index: start,
+            end,
+            _phantom: PhantomData,
         }
     }
 
-    /// Tries to recover the owning data.
+    /// Creates a chunk referencing to the `start..end` range of the owner.
     ///
-    /// The method succeeds if the referencing chunk iterator and all chunks are dropped.
-    /// Otherwise, it returns the guard intact.
-    pub fn try_unwrap_owner(self) -> Result<S, Self> {
-        let Self { owner, slice, .. } = self;
-        Arc::try_unwrap(owner).map_err(|owner| Self {
+    /// # Safety
+    /// The base pointer must be taken from the owner by [RawSlice::from_mut], and the caller
+    /// must make sure the range does not overlap with any other living chunk. The latter is
+    /// asserted with the `checked` feature.
+    pub(crate) unsafe fn from_owner_range(
+        owner: Owner<S>,
+        raw: RawSlice<T>,
+        start: usize,
+        end: usize,
+    ) -> Self {
+        assert!(start <= end && end <= raw.len());
+
+        ChunkMut {
+            tracker: Tracker::new(owner.addr(), start..end),
+            checker: Checker::new(owner.addr(), start..end),
             owner,
-            slice,
+            raw,
+            start,
+            end,
+            partition: None,
             _phantom: PhantomData,
-        })
+        }
     }
 
-    /// Returns an iterator of owned references to each element of the slice.
-    pub fn into_iter_owned(self) -> IterMut<'a, S, T> {
+    /// Moves the fields out of the chunk, which is consumed without being marked poisoned.
+    pub(crate) fn into_parts(self) -> ChunkMutParts<S, T> {
+        let this = ManuallyDrop::new(self);
+
+        // each field is read exactly once from the forgotten chunk
         unsafe {
-            let index = self.start_index();
-            let Self { owner, slice, .. } = self;
-            let end = index + slice.as_ref().len();
+            ChunkMutParts {
+                owner: ptr::read(&this.owner),
+                raw: this.raw,
+                start: this.start,
+                end: this.end,
+                partition: this.partition,
+                tracker: ptr::read(&this.tracker),
+                checker: ptr::read(&this.checker),
+            }
+        }
+    }
 
-            IterMut {
+    /// Gets the offset of the chunk in the owner.
+    pub fn offset(&self) -> usize {
+        self.start
+    }
+
+    /// Gets the range of the chunk in the owner.
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+
+    /// Gets the length of the owner.
+    pub fn owner_len(&self) -> usize {
+        self.raw.len()
+    }
+
+    /// Returns an iterator of mutable elements along with their indices in the owner.
+    pub fn enumerate_global(&mut self) -> iter::Zip<RangeFrom<usize>, slice::IterMut<'_, T>> {
+        (self.offset()..).zip(self.iter_mut())
+    }
+}
+
+impl<'a, S, T> ChunkMut<'a, S, T>
+where
+    S: ChunkOwnerMut<T> + Send + Sync + 'a,
+    T: Send + Sync,
+{
+    /// Tries to recover the owning data.
+    ///
+    /// The method succeeds if the referencing chunk iterator and all chunks are dropped.
+    /// Otherwise, it returns the guard intact. Like [Mutex](std::sync::Mutex), the data is
+    /// returned in [TryUnwrapError::Poisoned] if any chunk was dropped during panics, since
+    /// the chunk may be left half-written.
+    ///
+    /// [TryUnwrapError::Poisoned]: crate::TryUnwrapError::Poisoned
+    pub fn try_unwrap_owner(self) -> TryUnwrapResult<S, Self> {
+        let ChunkMutParts {
+            owner,
+            raw,
+            start,
+            end,
+            partition,
+            tracker,
+            checker,
+            ..
+        } = self.into_parts();
+        owner.try_recover().map_err(|err| {
+            err.map_shared(|owner| Self {
                 owner,
-                index,
+                raw,
+                start,
                 end,
+                partition,
+                tracker,
+                checker,
                 _phantom: PhantomData,
-            }
-        }
+            })
+        })
     }
+}
 
-    fn start_index(&self) -> usize {
-        unsafe {
-            let owner_ptr = Arc::as_ptr(&self.owner) as *mut S;
-            let owner_slice = owner_ptr.as_mut().unwrap().as_mut();
+impl<'a, S, T> ChunkMut<'a, OnRelease<S>, T>
+where
+    S: ChunkOwnerMut<T> + Send + Sync + 'a,
+    T: Send + Sync,
+{
+    /// Creates a chunk covering the whole owner, which invokes `on_release` with the owner
+    /// once the last chunk or chunk iterator referring to it is dropped. See [OnRelease].
+    pub fn with_on_release<F>(owner: S, on_release: F) -> Self
+    where
+        F: FnOnce(Result<S, Poisoned<S>>) + Send + 'static,
+    {
+        let state = Arc::<OwnerState>::default();
+        let mut owner = Arc::new(OnRelease::new(owner, state.clone(), on_release));
 
-            let slice_ptr = self.slice.as_ref().as_ptr();
-            slice_ptr.offset_from(owner_slice.as_ptr()) as usize
-        }
+        // the Arc was just created, so it is not shared
+        let raw = RawSlice::from_mut(Arc::get_mut(&mut owner).unwrap().as_mut_slice());
+        unsafe { Self::from_owner_range(Owner::with_state(owner, state), raw, 0, raw.len()) }
+    }
+}
+
+impl<'a, S, T> Drop for ChunkMut<'a, S, T>
+where
+    S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
+    T: Send + Sync,
+{
+    fn drop(&mut self) {
+        self.owner.poison_on_panic(self.start..self.end);
     }
 }
 
 unsafe impl<'a, S, T> Send for ChunkMut<'a, S, T>
 where
-    S: AsMut<[T]> + Send + Sync + 'a,
+    S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
     T: Send + Sync,
 {
 }
 
 unsafe impl<'a, S, T> Sync for ChunkMut<'a, S, T>
 where
-    S: AsMut<[T]> + Send + Sync + 'a,
+    S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
     T: Send + Sync,
 {
 }
 
 impl<'a, S, T> AsRef<[T]> for ChunkMut<'a, S, T>
 where
-    S: AsMut<[T]> + Send + Sync + 'a,
+    S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
     T: Send + Sync,
 {
     fn as_ref(&self) -> &[T] {
@@ -286,7 +1047,7 @@ where
 
 impl<'a, S, T> AsMut<[T]> for ChunkMut<'a, S, T>
 where
-    S: AsMut<[T]> + Send + Sync + 'a,
+    S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
     T: Send + Sync,
 {
     fn as_mut(&mut self) -> &mut [T] {
@@ -296,29 +1057,29 @@ where
 
 impl<'a, S, T> Deref for ChunkMut<'a, S, T>
 where
-    S: AsMut<[T]> + Send + Sync + 'a,
+    S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
     T: Send + Sync,
 {
     type Target = [T];
 
     fn deref(&self) -> &Self::Target {
-        unsafe { self.slice.as_ref() }
+        unsafe { self.raw.slice(self.start, self.end) }
     }
 }
 
 impl<'a, S, T> DerefMut for ChunkMut<'a, S, T>
 where
-    S: AsMut<[T]> + Send + Sync + 'a,
+    S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
     T: Send + Sync,
 {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        unsafe { self.slice.as_mut() }
+        unsafe { self.raw.slice_mut(self.start, self.end) }
     }
 }
 
 impl<'a, S, T> IntoIterator for &'a ChunkMut<'_, S, T>
 where
-    S: AsMut<[T]> + Send + Sync + 'a,
+    S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
     T: Send + Sync,
 {
     type Item = &'a T;
@@ -331,7 +1092,7 @@ where
 
 impl<'a, S, T> IntoIterator for &'a mut ChunkMut<'_, S, T>
 where
-    S: AsMut<[T]> + Send + Sync + 'a,
+    S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
     T: Send + Sync,
 {
     type Item = &'a mut T;
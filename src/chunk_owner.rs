@@ -0,0 +1,123 @@
+use alloc::{boxed::Box, string::String, vec::Vec};
+
+/// A slice-like owner that can be shared by [chunks](crate::Chunk).
+///
+/// The base pointer of the slice is taken once when the owner is wrapped into a chunk,
+/// and all chunks address their elements from it afterwards.
+///
+/// # Safety
+/// The implementation must guarantee that [as_slice()](ChunkOwner::as_slice) returns the
+/// same pointer and length on every call, and the slice stays valid until the owner is
+/// dropped or moved. That is, the slice must not be reallocated, shrunk or replaced through
+/// a shared reference to the owner.
+pub unsafe trait ChunkOwner<T> {
+    /// Gets the slice of the owner.
+    fn as_slice(&self) -> &[T];
+}
+
+/// A slice-like owner that can be divided into [mutable chunks](crate::ChunkMut).
+///
+/// # Safety
+/// Besides the requirements of [ChunkOwner], the implementation must guarantee that
+/// [as_mut_slice()](ChunkOwnerMut::as_mut_slice) returns the same pointer and length as
+/// [as_slice()](ChunkOwner::as_slice), and the slice stays valid until the owner is dropped
+/// or moved.
+pub unsafe trait ChunkOwnerMut<T>: ChunkOwner<T> {
+    /// Gets the mutable slice of the owner.
+    fn as_mut_slice(&mut self) -> &mut [T];
+}
+
+unsafe impl<T> ChunkOwner<T> for [T] {
+    fn as_slice(&self) -> &[T] {
+        self
+    }
+}
+
+unsafe impl<T> ChunkOwnerMut<T> for [T] {
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+/// The bytes of a string. There is no mutable counterpart since writing arbitrary bytes
+/// could break the UTF-8 encoding.
+unsafe impl ChunkOwner<u8> for str {
+    fn as_slice(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+unsafe impl ChunkOwner<u8> for String {
+    fn as_slice(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+unsafe impl<T, const N: usize> ChunkOwner<T> for [T; N] {
+    fn as_slice(&self) -> &[T] {
+        self
+    }
+}
+
+unsafe impl<T, const N: usize> ChunkOwnerMut<T> for [T; N] {
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+unsafe impl<T> ChunkOwner<T> for Vec<T> {
+    fn as_slice(&self) -> &[T] {
+        self
+    }
+}
+
+unsafe impl<T> ChunkOwnerMut<T> for Vec<T> {
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+unsafe impl<T, S> ChunkOwner<T> for Box<S>
+where
+    S: ChunkOwner<T> + ?Sized,
+{
+    fn as_slice(&self) -> &[T] {
+        (**self).as_slice()
+    }
+}
+
+unsafe impl<T, S> ChunkOwnerMut<T> for Box<S>
+where
+    S: ChunkOwnerMut<T> + ?Sized,
+{
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        (**self).as_mut_slice()
+    }
+}
+
+unsafe impl<T, S> ChunkOwner<T> for &S
+where
+    S: ChunkOwner<T> + ?Sized,
+{
+    fn as_slice(&self) -> &[T] {
+        (**self).as_slice()
+    }
+}
+
+unsafe impl<T, S> ChunkOwner<T> for &mut S
+where
+    S: ChunkOwner<T> + ?Sized,
+{
+    fn as_slice(&self) -> &[T] {
+        (**self).as_slice()
+    }
+}
+
+unsafe impl<T, S> ChunkOwnerMut<T> for &mut S
+where
+    S: ChunkOwnerMut<T> + ?Sized,
+{
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        (**self).as_mut_slice()
+    }
+}
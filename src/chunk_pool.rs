@@ -0,0 +1,122 @@
+use crate::{chunk_mut::ChunkMut, common::*, on_release::OnRelease, poison::Poisoned, utils};
+use alloc::{boxed::Box, sync::Weak};
+use std::sync::Mutex;
+
+/// A pool of owners that are recycled once all chunks derived from them are dropped.
+///
+/// [chunk_mut()](ChunkPool::chunk_mut) hands out a chunk covering a pooled owner, or a newly
+/// created one if the pool is empty. Once the last chunk or chunk iterator derived from it is
+/// dropped, the owner is passed to the reset hook and returned to the pool, unless the pool is
+/// full or already dropped. Owners poisoned by chunks dropped during panics are not recycled.
+pub struct ChunkPool<S> {
+    inner: Arc<PoolInner<S>>,
+}
+
+type CreateFn<S> = Box<dyn Fn() -> S + Send + Sync>;
+type ResetFn<S> = Box<dyn Fn(&mut S) + Send + Sync>;
+
+struct PoolInner<S> {
+    capacity: usize,
+    idle: Mutex<Vec<S>>,
+    create: CreateFn<S>,
+    reset: Option<ResetFn<S>>,
+}
+
+impl<S> ChunkPool<S>
+where
+    S: Send + Sync + 'static,
+{
+    /// Creates a pool keeping at most `capacity` idle owners, where `create` is called to
+    /// create an owner when the pool is empty.
+    pub fn new<F>(capacity: usize, create: F) -> Self
+    where
+        F: Fn() -> S + Send + Sync + 'static,
+    {
+        Self {
+            inner: Arc::new(PoolInner {
+                capacity,
+                idle: Mutex::new(Vec::with_capacity(capacity)),
+                create: Box::new(create),
+                reset: None,
+            }),
+        }
+    }
+
+    /// Sets the hook called on each owner before it is returned to the pool.
+    ///
+    /// # Panics
+    /// The method panics if the pool is cloned or chunks were already handed out from it.
+    pub fn with_reset<F>(mut self, reset: F) -> Self
+    where
+        F: Fn(&mut S) + Send + Sync + 'static,
+    {
+        let inner = Arc::get_mut(&mut self.inner)
+            .expect("the reset hook must be set before the pool is shared");
+        inner.reset = Some(Box::new(reset));
+        self
+    }
+
+    /// Takes an owner from the pool and returns a chunk covering it.
+    pub fn chunk_mut<T>(&self) -> ChunkMut<'static, OnRelease<S>, T>
+    where
+        S: ChunkOwnerMut<T>,
+        T: Send + Sync,
+    {
+        let owner = utils::lock(&self.inner.idle).pop();
+        let owner = owner.unwrap_or_else(|| (self.inner.create)());
+
+        let pool = Arc::downgrade(&self.inner);
+        ChunkMut::with_on_release(owner, move |owner| PoolInner::recycle(&pool, owner))
+    }
+
+    /// Gets the maximum number of idle owners kept in the pool.
+    pub fn capacity(&self) -> usize {
+        self.inner.capacity
+    }
+
+    /// Gets the number of idle owners in the pool.
+    pub fn len(&self) -> usize {
+        utils::lock(&self.inner.idle).len()
+    }
+
+    /// Checks if there is no idle owner in the pool.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<S> PoolInner<S> {
+    fn recycle(pool: &Weak<Self>, owner: Result<S, Poisoned<S>>) {
+        // the poisoned owner may be left half-written
+        let (pool, mut owner) = match (pool.upgrade(), owner) {
+            (Some(pool), Ok(owner)) => (pool, owner),
+            _ => return,
+        };
+
+        if let Some(reset) = &pool.reset {
+            reset(&mut owner);
+        }
+
+        let mut idle = utils::lock(&pool.idle);
+        if idle.len() < pool.capacity {
+            idle.push(owner);
+        }
+    }
+}
+
+impl<S> Clone for ChunkPool<S> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<S> Debug for ChunkPool<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChunkPool")
+            .field("capacity", &self.inner.capacity)
+            .field("idle", &utils::lock(&self.inner.idle).len())
+            .finish_non_exhaustive()
+    }
+}
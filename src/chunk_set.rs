@@ -0,0 +1,332 @@
+use crate::{
+    chunk_mut::ChunkMut,
+    common::*,
+    error::{ChunkError, TryUnwrapError, TryUnwrapResult},
+    owner::Owner,
+    partition::Partition,
+    raw::RawSlice,
+};
+use alloc::collections::BTreeMap;
+use core::ops::Range;
+
+/// A collection of [chunks](ChunkMut) of one owner, ordered by their ranges in the owner.
+///
+/// It is useful to collect chunks coming back from workers, find out the missing
+/// ranges, and recover the owner once all chunks are collected.
+#[derive(Debug)]
+pub struct ChunkSet<'a, S, T>
+where
+    S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
+    T: Send + Sync,
+{
+    owner: Option<Owner<S>>,
+    owner_len: usize,
+    chunks: BTreeMap<Key, ChunkMut<'a, S, T>>,
+    next_seq: usize,
+}
+
+/// The start, end and insertion sequence number of a chunk, where the sequence number
+/// tells apart empty chunks at the same offset.
+type Key = (usize, usize, usize);
+
+/// The ranges and partitions of the chunks dropped from a set, from which the set is
+/// restored if the owner turns out to be shared.
+struct Layout<T> {
+    owner_len: usize,
+    raw: Option<RawSlice<T>>,
+    chunks: Vec<(usize, usize, Option<Partition>)>,
+}
+
+impl<'a, S, T> ChunkSet<'a, S, T>
+where
+    S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
+    T: Send + Sync,
+{
+    /// Creates an empty set. The owner is determined by the first inserted chunk.
+    pub fn new() -> Self {
+        Self {
+            owner: None,
+            owner_len: 0,
+            chunks: BTreeMap::new(),
+            next_seq: 0,
+        }
+    }
+
+    /// Inserts a chunk to the set.
+    ///
+    /// It returns the chunk back in the error if the chunk belongs to an owner different
+    /// from the chunks in the set. Empty chunks are kept, even if they share an offset.
+    pub fn insert(
+        &mut self,
+        chunk: ChunkMut<'a, S, T>,
+    ) -> Result<(), ChunkError<ChunkMut<'a, S, T>>> {
+        match &self.owner {
+            Some(owner) => {
+                if !owner.ptr_eq(&chunk.owner) {
+                    return Err(ChunkError::ForeignOwner { chunk });
+                }
+            }
+            None => {
+                self.owner = Some(chunk.owner.clone());
+                self.owner_len = chunk.owner_len();
+            }
+        }
+
+        let key = self.next_key(chunk.range());
+        self.chunks.insert(key, chunk);
+        Ok(())
+    }
+
+    /// Gets the chunk containing the element at `index` of the owner.
+    pub fn get(&self, index: usize) -> Option<&ChunkMut<'a, S, T>> {
+        let key = self.find(index)?;
+        self.chunks.get(&key)
+    }
+
+    /// Gets the mutable chunk containing the element at `index` of the owner.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut ChunkMut<'a, S, T>> {
+        let key = self.find(index)?;
+        self.chunks.get_mut(&key)
+    }
+
+    /// Removes and returns the chunk containing the element at `index` of the owner.
+    pub fn remove(&mut self, index: usize) -> Option<ChunkMut<'a, S, T>> {
+        let key = self.find(index)?;
+        self.chunks.remove(&key)
+    }
+
+    /// Gets the number of chunks in the set.
+    pub fn len(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// Returns true if the set has no chunks.
+    pub fn is_empty(&self) -> bool {
+        self.chunks.is_empty()
+    }
+
+    /// Returns an iterator of the chunks ordered by their ranges.
+    pub fn iter(&self) -> impl Iterator<Item = &ChunkMut<'a, S, T>> {
+        self.chunks.values()
+    }
+
+    /// Returns the ranges of the owner covered by the chunks, where adjacent ranges are merged.
+    pub fn covered_ranges(&self) -> Vec<Range<usize>> {
+        let mut ranges: Vec<Range<usize>> = vec![];
+
+        self.chunks
+            .keys()
+            .filter(|&&(start, end, _)| start < end)
+            .for_each(|&(start, end, _)| match ranges.last_mut() {
+                Some(last) if last.end == start => last.end = end,
+                _ => ranges.push(start..end),
+            });
+
+        ranges
+    }
+
+    /// Returns the ranges of the owner not covered by any chunk.
+    pub fn missing_ranges(&self) -> Vec<Range<usize>> {
+        let mut missing = vec![];
+        let mut index = 0;
+
+        for range in self.covered_ranges() {
+            if index < range.start {
+                missing.push(index..range.start);
+            }
+            index = range.end;
+        }
+        if index < self.owner_len {
+            missing.push(index..self.owner_len);
+        }
+
+        missing
+    }
+
+    /// Returns true if the chunks cover the whole owner.
+    pub fn is_complete(&self) -> bool {
+        self.owner.is_some() && self.missing_ranges().is_empty()
+    }
+
+    /// Concatenates each run of adjacent chunks into one chunk.
+    pub fn merge_adjacent(&mut self) {
+        let chunks = mem::take(&mut self.chunks);
+        let mut runs: Vec<Vec<ChunkMut<'a, S, T>>> = vec![];
+        let mut run_end = None;
+
+        for ((start, end, _), chunk) in chunks {
+            match runs.last_mut() {
+                Some(run) if run_end == Some(start) => run.push(chunk),
+                _ => runs.push(vec![chunk]),
+            }
+            run_end = Some(end);
+        }
+
+        for run in runs {
+            let chunk = ChunkMut::cat(run);
+            let key = self.next_key(chunk.range());
+            self.chunks.insert(key, chunk);
+        }
+    }
+
+    /// Takes back all chunks in the set as a chunk covering the whole owner for a new
+    /// partition. See [repartition()](ChunkMut::repartition).
+    ///
+    /// It returns the set intact if the chunks do not cover the whole owner.
+    pub fn repartition(self) -> Result<ChunkMut<'a, S, T>, Self> {
+        if !self.is_complete() {
+            return Err(self);
+        }
+
+        match ChunkMut::repartition(self.into_chunks()) {
+            Ok(chunk) => Ok(chunk),
+            Err(_) => unreachable!("the chunks must cover the whole owner"),
+        }
+    }
+
+    /// Takes all chunks out of the set, ordered by their ranges.
+    pub fn into_chunks(self) -> Vec<ChunkMut<'a, S, T>> {
+        self.chunks.into_values().collect()
+    }
+
+    /// Lists the chunks referring to the owner of the set, which are still alive. It
+    /// includes the chunks inside and outside the set.
+    #[cfg(feature = "tracking")]
+    pub fn live_chunks(&self) -> Vec<ChunkInfo> {
+        self.owner
+            .as_ref()
+            .map_or_else(Vec::new, |owner| owner.live_chunks())
+    }
+
+    /// Tries to recover the owning data.
+    ///
+    /// The method succeeds if the chunks cover the whole owner and no other chunk or
+    /// chunk iterator refers to the owner. Otherwise, it returns the set intact. The data is
+    /// returned in [TryUnwrapError::Poisoned] if any chunk was dropped during panics.
+    pub fn try_unwrap_owner(self) -> TryUnwrapResult<S, Self>
+    where
+        S: Sized,
+    {
+        let (owner, layout) = self.take_unique_owner()?;
+
+        owner
+            .try_recover()
+            .map_err(|err| err.map_shared(|owner| Self::restore(owner, layout)))
+    }
+
+    /// Tries to recover the reference-counted owner, which also works for unsized owners
+    /// such as `[T]`.
+    ///
+    /// Like [try_unwrap_owner()](ChunkSet::try_unwrap_owner), the method succeeds if the
+    /// chunks cover the whole owner and no other chunk or chunk iterator refers to the
+    /// owner. Otherwise, it returns the set intact.
+    pub fn try_unwrap_arc(self) -> TryUnwrapResult<Arc<S>, Self> {
+        let (owner, layout) = self.take_unique_owner()?;
+
+        owner
+            .try_recover_arc()
+            .map_err(|err| err.map_shared(|owner| Self::restore(owner, layout)))
+    }
+
+    /// Finds the key of the non-empty chunk containing the element at `index`.
+    fn find(&self, index: usize) -> Option<Key> {
+        // the non-empty chunks are disjoint, so only the last one starting at or before
+        // `index` can contain it
+        self.chunks
+            .range(..=(index, usize::MAX, usize::MAX))
+            .rev()
+            .map(|(&key, _)| key)
+            .find(|&(start, end, _)| start < end)
+            .filter(|&(_, end, _)| index < end)
+    }
+
+    fn next_key(&mut self, range: Range<usize>) -> Key {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        (range.start, range.end, seq)
+    }
+
+    fn take_unique_owner<D>(self) -> Result<(Owner<S>, Layout<T>), TryUnwrapError<D, Self>> {
+        let owner = match &self.owner {
+            Some(owner) => owner,
+            None => return Err(TryUnwrapError::Shared(self)),
+        };
+
+        // the set and each chunk in it hold one reference
+        if !self.is_complete() || owner.ref_count() != self.chunks.len() + 1 {
+            return Err(TryUnwrapError::Shared(self));
+        }
+
+        let Self {
+            owner,
+            owner_len,
+            chunks,
+            ..
+        } = self;
+        let layout = Layout {
+            owner_len,
+            raw: chunks.values().next().map(|chunk| chunk.raw),
+            chunks: chunks
+                .into_values()
+                .map(|chunk| (chunk.start, chunk.end, chunk.partition))
+                .collect(),
+        };
+        Ok((owner.unwrap(), layout))
+    }
+
+    /// Restores the set from the layout of the chunks dropped by
+    /// [take_unique_owner()](ChunkSet::take_unique_owner), in case a reference to the owner
+    /// was taken in the meantime.
+    fn restore(owner: Owner<S>, layout: Layout<T>) -> Self {
+        let Layout {
+            owner_len,
+            raw,
+            chunks,
+        } = layout;
+        let mut set = Self::new();
+
+        for (start, end, partition) in chunks {
+            // the dropped chunks covered the ranges exclusively
+            let raw = raw.expect("the raw slice is taken from the chunks");
+            let mut chunk = unsafe { ChunkMut::from_owner_range(owner.clone(), raw, start, end) };
+            chunk.partition = partition;
+            let key = set.next_key(chunk.range());
+            set.chunks.insert(key, chunk);
+        }
+
+        set.owner = Some(owner);
+        set.owner_len = owner_len;
+        set
+    }
+}
+
+impl<'a, S, T> Default for ChunkSet<'a, S, T>
+where
+    S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
+    T: Send + Sync,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, S, T> Extend<ChunkMut<'a, S, T>> for ChunkSet<'a, S, T>
+where
+    S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
+    T: Send + Sync,
+{
+    /// Inserts the chunks to the set.
+    ///
+    /// # Panics
+    /// The method panics if a chunk belongs to a foreign owner.
+    fn extend<I>(&mut self, chunks: I)
+    where
+        I: IntoIterator<Item = ChunkMut<'a, S, T>>,
+    {
+        chunks.into_iter().for_each(|chunk| {
+            if let Err(err) = self.insert(chunk) {
+                panic!("{}", err);
+            }
+        });
+    }
+}
@@ -1,4 +1,52 @@
-use crate::{chunk::Chunk, common::*};
+use crate::{chunk::Chunk, common::*, owner::Owner, partition::PartitionCounter, raw::RawSlice};
+
+/// Implements the methods to access the owner on a chunk iterator, given the fields other
+/// than `owner` and `_phantom` to rebuild the iterator with.
+macro_rules! impl_owner_methods {
+    ($($field:ident),* $(,)?) => {
+        /// Converts to the reference-counted owner.
+        pub fn into_arc_owner(self) -> Arc<S> {
+            // shared chunks never mutate the data
+            unsafe { self.owner.into_arc() }
+        }
+
+        /// Tries to recover the owning data.
+        ///
+        /// The method succeeds if the referencing chunk iterator and all chunks are dropped.
+        /// Otherwise, it returns the iterator intact.
+        pub fn try_unwrap_owner(self) -> Result<S, Self>
+        where
+            S: Sized,
+        {
+            let Self { owner, $($field,)* .. } = self;
+
+            owner.try_unwrap().map_err(|owner| Self {
+                owner,
+                $($field,)*
+                _phantom: PhantomData,
+            })
+        }
+
+        /// Gets the reference count on the owning data.
+        pub fn ref_count(&self) -> usize {
+            self.owner.ref_count()
+        }
+
+        /// Lists the chunks referring to the owning data, which are still alive.
+        ///
+        /// Chunk iterators and owned elements referring to the data are not listed.
+        #[cfg(feature = "tracking")]
+        pub fn live_chunks(&self) -> Vec<ChunkInfo> {
+            self.owner.live_chunks()
+        }
+
+        /// Returns an iterator that stops yielding chunks once the owning data is cancelled
+        /// by [cancel()](Chunk::cancel).
+        pub fn cancellable(self) -> Cancellable<Self> {
+            Cancellable::new(self.owner.state().clone(), self)
+        }
+    };
+}
 
 pub use sized_chunks::*;
 mod sized_chunks {
@@ -8,56 +56,29 @@ mod sized_chunks {
     #[derive(Debug)]
     pub struct SizedChunks<'a, S, T>
     where
-        S: AsRef<[T]> + Send + Sync + 'a,
+        S: ChunkOwner<T> + Send + Sync + ?Sized + 'a,
         T: Send + Sync,
     {
         pub(crate) index: usize,
         pub(crate) chunk_size: usize,
         pub(crate) end: usize,
-        pub(crate) owner: Arc<S>,
+        pub(crate) partition: PartitionCounter,
+        pub(crate) owner: Owner<S>,
+        pub(crate) raw: RawSlice<T>,
         pub(crate) _phantom: PhantomData<&'a T>,
     }
 
     impl<'a, S, T> SizedChunks<'a, S, T>
     where
-        S: AsRef<[T]> + Send + Sync + 'a,
+        S: ChunkOwner<T> + Send + Sync + ?Sized + 'a,
         T: Send + Sync,
     {
-        pub fn into_arc_owner(self) -> Arc<S> {
-            self.owner
-        }
-
-        /// Tries to recover the owning data.
-        ///
-        /// The method succeeds if the referencing chunk iterator and all chunks are dropped.
-        /// Otherwise, it returns the guard intact.
-        pub fn try_unwrap_owner(self) -> Result<S, Self> {
-            let Self {
-                index,
-                chunk_size,
-                end,
-                owner,
-                ..
-            } = self;
-
-            Arc::try_unwrap(owner).map_err(|owner| Self {
-                index,
-                chunk_size,
-                end,
-                owner,
-                _phantom: PhantomData,
-            })
-        }
-
-        /// Gets the reference count on the owning data.
-        pub fn ref_count(&self) -> usize {
-            Arc::strong_count(&self.owner)
-        }
+        impl_owner_methods!(index, chunk_size, end, partition, raw);
     }
 
     impl<'a, S, T> Iterator for SizedChunks<'a, S, T>
     where
-        S: AsRef<[T]> + Send + Sync + 'a,
+        S: ChunkOwner<T> + Send + Sync + ?Sized + 'a,
         T: Send + Sync,
     {
         type Item = Chunk<'a, S, T>;
@@ -68,22 +89,14 @@ mod sized_chunks {
             }
 
             let start = self.index;
-            let end = cmp::min(start + self.chunk_size, self.end);
+            let end = start + cmp::min(self.chunk_size, self.end - start);
             self.index = end;
 
-            let owner = self.owner.clone();
+            let mut chunk = Chunk::from_owner_range(self.owner.clone(), self.raw, start, end);
 
-            let slice = unsafe {
-                let ptr = Arc::as_ptr(&owner);
-                let slice: &[T] = ptr.as_ref().unwrap().as_ref();
-                NonNull::new_unchecked(&slice[start..end] as *const [T] as *mut [T])
-            };
+            chunk.partition = Some(self.partition.next_partition());
 
-            Some(Chunk {
-                owner,
-                slice,
-                _phantom: PhantomData,
-            })
+            Some(chunk)
         }
     }
 }
@@ -96,59 +109,30 @@ mod even_chunks {
     #[derive(Debug)]
     pub struct EvenChunks<'a, S, T>
     where
-        S: AsRef<[T]> + Send + Sync + 'a,
+        S: ChunkOwner<T> + Send + Sync + ?Sized + 'a,
         T: Send + Sync,
     {
         pub(crate) base_chunk_size: usize,
         pub(crate) index: usize,
         pub(crate) long_end: usize,
         pub(crate) short_end: usize,
-        pub(crate) owner: Arc<S>,
+        pub(crate) partition: PartitionCounter,
+        pub(crate) owner: Owner<S>,
+        pub(crate) raw: RawSlice<T>,
         pub(crate) _phantom: PhantomData<&'a T>,
     }
 
     impl<'a, S, T> EvenChunks<'a, S, T>
     where
-        S: AsRef<[T]> + Send + Sync + 'a,
+        S: ChunkOwner<T> + Send + Sync + ?Sized + 'a,
         T: Send + Sync,
     {
-        pub fn into_arc_owner(self) -> Arc<S> {
-            self.owner
-        }
-
-        /// Tries to recover the owning data.
-        ///
-        /// The method succeeds if the referencing chunk iterator and all chunks are dropped.
-        /// Otherwise, it returns the guard intact.
-        pub fn try_unwrap_owner(self) -> Result<S, Self> {
-            let Self {
-                index,
-                base_chunk_size,
-                long_end,
-                short_end,
-                owner,
-                ..
-            } = self;
-
-            Arc::try_unwrap(owner).map_err(|owner| Self {
-                index,
-                base_chunk_size,
-                long_end,
-                short_end,
-                owner,
-                _phantom: PhantomData,
-            })
-        }
-
-        /// Gets the reference count on the owning data.
-        pub fn ref_count(&self) -> usize {
-            Arc::strong_count(&self.owner)
-        }
+        impl_owner_methods!(index, base_chunk_size, long_end, short_end, partition, raw);
     }
 
     impl<'a, S, T> Iterator for EvenChunks<'a, S, T>
     where
-        S: AsRef<[T]> + Send + Sync + 'a,
+        S: ChunkOwner<T> + Send + Sync + ?Sized + 'a,
         T: Send + Sync,
     {
         type Item = Chunk<'a, S, T>;
@@ -173,18 +157,474 @@ mod even_chunks {
                     || (start < self.short_end && end <= self.short_end)
             );
 
-            let owner = self.owner.clone();
-            let slice = unsafe {
-                let ptr = Arc::as_ptr(&owner) as *mut S;
-                let slice: &[T] = ptr.as_ref().unwrap().as_ref();
-                NonNull::new_unchecked(&slice[start..end] as *const [T] as *mut [T])
-            };
+            let mut chunk = Chunk::from_owner_range(self.owner.clone(), self.raw, start, end);
+
+            chunk.partition = Some(self.partition.next_partition());
+
+            Some(chunk)
+        }
+    }
+}
+
+pub use exact_chunks::*;
+mod exact_chunks {
+    use super::*;
+
+    /// An iterator that yields [chunks](Chunk) of exactly the same size.
+    #[derive(Debug)]
+    pub struct ExactChunks<'a, S, T>
+    where
+        S: ChunkOwner<T> + Send + Sync + ?Sized + 'a,
+        T: Send + Sync,
+    {
+        pub(crate) index: usize,
+        pub(crate) chunk_size: usize,
+        pub(crate) end: usize,
+        pub(crate) rem_end: usize,
+        pub(crate) partition: PartitionCounter,
+        pub(crate) owner: Owner<S>,
+        pub(crate) raw: RawSlice<T>,
+        pub(crate) _phantom: PhantomData<&'a T>,
+    }
+
+    impl<'a, S, T> ExactChunks<'a, S, T>
+    where
+        S: ChunkOwner<T> + Send + Sync + ?Sized + 'a,
+        T: Send + Sync,
+    {
+        /// Returns the trailing elements that do not fill a whole chunk.
+        ///
+        /// The chunks not yet yielded by the iterator are discarded.
+        pub fn into_remainder(self) -> Chunk<'a, S, T> {
+            let Self {
+                end,
+                rem_end,
+                owner,
+                raw,
+                ..
+            } = self;
+            Chunk::from_owner_range(owner, raw, end, rem_end)
+        }
+
+        impl_owner_methods!(index, chunk_size, end, rem_end, partition, raw);
+    }
+
+    impl<'a, S, T> Iterator for ExactChunks<'a, S, T>
+    where
+        S: ChunkOwner<T> + Send + Sync + ?Sized + 'a,
+        T: Send + Sync,
+    {
+        type Item = Chunk<'a, S, T>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            if self.index >= self.end {
+                return None;
+            }
+
+            let start = self.index;
+            let end = start + self.chunk_size;
+            self.index = end;
+
+            let mut chunk = Chunk::from_owner_range(self.owner.clone(), self.raw, start, end);
+            chunk.partition = Some(self.partition.next_partition());
+            Some(chunk)
+        }
+    }
+}
 
-            Some(Chunk {
+pub use rchunks::*;
+mod rchunks {
+    use super::*;
+
+    /// An iterator that yields [chunks](Chunk) starting from the end of the slice.
+    #[derive(Debug)]
+    pub struct RChunks<'a, S, T>
+    where
+        S: ChunkOwner<T> + Send + Sync + ?Sized + 'a,
+        T: Send + Sync,
+    {
+        pub(crate) start: usize,
+        pub(crate) chunk_size: usize,
+        pub(crate) index: usize,
+        pub(crate) partition: PartitionCounter,
+        pub(crate) owner: Owner<S>,
+        pub(crate) raw: RawSlice<T>,
+        pub(crate) _phantom: PhantomData<&'a T>,
+    }
+
+    impl<'a, S, T> RChunks<'a, S, T>
+    where
+        S: ChunkOwner<T> + Send + Sync + ?Sized + 'a,
+        T: Send + Sync,
+    {
+        impl_owner_methods!(start, chunk_size, index, partition, raw);
+    }
+
+    impl<'a, S, T> Iterator for RChunks<'a, S, T>
+    where
+        S: ChunkOwner<T> + Send + Sync + ?Sized + 'a,
+        T: Send + Sync,
+    {
+        type Item = Chunk<'a, S, T>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            if self.index <= self.start {
+                return None;
+            }
+
+            let end = self.index;
+            let start = cmp::max(self.start, end.saturating_sub(self.chunk_size));
+            self.index = start;
+
+            let mut chunk = Chunk::from_owner_range(self.owner.clone(), self.raw, start, end);
+            chunk.partition = Some(self.partition.next_partition());
+            Some(chunk)
+        }
+    }
+}
+
+pub use rchunks_exact::*;
+mod rchunks_exact {
+    use super::*;
+
+    /// An iterator that yields [chunks](Chunk) of exactly the same size starting from the end of
+    /// the slice.
+    #[derive(Debug)]
+    pub struct RChunksExact<'a, S, T>
+    where
+        S: ChunkOwner<T> + Send + Sync + ?Sized + 'a,
+        T: Send + Sync,
+    {
+        pub(crate) rem_start: usize,
+        pub(crate) start: usize,
+        pub(crate) chunk_size: usize,
+        pub(crate) index: usize,
+        pub(crate) partition: PartitionCounter,
+        pub(crate) owner: Owner<S>,
+        pub(crate) raw: RawSlice<T>,
+        pub(crate) _phantom: PhantomData<&'a T>,
+    }
+
+    impl<'a, S, T> RChunksExact<'a, S, T>
+    where
+        S: ChunkOwner<T> + Send + Sync + ?Sized + 'a,
+        T: Send + Sync,
+    {
+        /// Returns the leading elements that do not fill a whole chunk.
+        ///
+        /// The chunks not yet yielded by the iterator are discarded.
+        pub fn into_remainder(self) -> Chunk<'a, S, T> {
+            let Self {
+                rem_start,
+                start,
                 owner,
-                slice,
-                _phantom: PhantomData,
-            })
+                raw,
+                ..
+            } = self;
+            Chunk::from_owner_range(owner, raw, rem_start, start)
+        }
+
+        impl_owner_methods!(rem_start, start, chunk_size, index, partition, raw);
+    }
+
+    impl<'a, S, T> Iterator for RChunksExact<'a, S, T>
+    where
+        S: ChunkOwner<T> + Send + Sync + ?Sized + 'a,
+        T: Send + Sync,
+    {
+        type Item = Chunk<'a, S, T>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            if self.index <= self.start {
+                return None;
+            }
+
+            let end = self.index;
+            let start = end - self.chunk_size;
+            self.index = start;
+
+            let mut chunk = Chunk::from_owner_range(self.owner.clone(), self.raw, start, end);
+            chunk.partition = Some(self.partition.next_partition());
+            Some(chunk)
+        }
+    }
+}
+
+pub use split::*;
+mod split {
+    use super::*;
+
+    /// An iterator that yields [chunks](Chunk) separated by elements that match a predicate.
+    #[derive(Debug)]
+    pub struct Split<'a, S, T, P>
+    where
+        S: ChunkOwner<T> + Send + Sync + ?Sized + 'a,
+        T: Send + Sync,
+        P: FnMut(&T) -> bool,
+    {
+        pub(crate) index: usize,
+        pub(crate) end: usize,
+        pub(crate) finished: bool,
+        pub(crate) pred: P,
+        pub(crate) owner: Owner<S>,
+        pub(crate) raw: RawSlice<T>,
+        pub(crate) _phantom: PhantomData<&'a T>,
+    }
+
+    impl<'a, S, T, P> Split<'a, S, T, P>
+    where
+        S: ChunkOwner<T> + Send + Sync + ?Sized + 'a,
+        T: Send + Sync,
+        P: FnMut(&T) -> bool,
+    {
+        impl_owner_methods!(index, end, finished, pred, raw);
+    }
+
+    impl<'a, S, T, P> Iterator for Split<'a, S, T, P>
+    where
+        S: ChunkOwner<T> + Send + Sync + ?Sized + 'a,
+        T: Send + Sync,
+        P: FnMut(&T) -> bool,
+    {
+        type Item = Chunk<'a, S, T>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            if self.finished {
+                return None;
+            }
+
+            let start = self.index;
+            let pos = unsafe {
+                let slice: &[T] = self.raw.slice(start, self.end);
+                slice.iter().position(&mut self.pred)
+            };
+
+            let end = match pos {
+                Some(pos) => {
+                    self.index = start + pos + 1;
+                    start + pos
+                }
+                None => {
+                    self.finished = true;
+                    self.index = self.end;
+                    self.end
+                }
+            };
+
+            Some(Chunk::from_owner_range(
+                self.owner.clone(),
+                self.raw,
+                start,
+                end,
+            ))
+        }
+    }
+}
+
+pub use splitn::*;
+mod splitn {
+    use super::*;
+
+    /// An iterator that yields at most a limited number of [chunks](Chunk) separated by elements
+    /// that match a predicate.
+    #[derive(Debug)]
+    pub struct SplitN<'a, S, T, P>
+    where
+        S: ChunkOwner<T> + Send + Sync + ?Sized + 'a,
+        T: Send + Sync,
+        P: FnMut(&T) -> bool,
+    {
+        pub(crate) index: usize,
+        pub(crate) end: usize,
+        pub(crate) count: usize,
+        pub(crate) pred: P,
+        pub(crate) owner: Owner<S>,
+        pub(crate) raw: RawSlice<T>,
+        pub(crate) _phantom: PhantomData<&'a T>,
+    }
+
+    impl<'a, S, T, P> SplitN<'a, S, T, P>
+    where
+        S: ChunkOwner<T> + Send + Sync + ?Sized + 'a,
+        T: Send + Sync,
+        P: FnMut(&T) -> bool,
+    {
+        impl_owner_methods!(index, end, count, pred, raw);
+    }
+
+    impl<'a, S, T, P> Iterator for SplitN<'a, S, T, P>
+    where
+        S: ChunkOwner<T> + Send + Sync + ?Sized + 'a,
+        T: Send + Sync,
+        P: FnMut(&T) -> bool,
+    {
+        type Item = Chunk<'a, S, T>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            if self.count == 0 {
+                return None;
+            }
+            self.count -= 1;
+
+            let start = self.index;
+            let pos = if self.count == 0 {
+                None
+            } else {
+                unsafe {
+                    let slice: &[T] = self.raw.slice(start, self.end);
+                    slice.iter().position(&mut self.pred)
+                }
+            };
+
+            let end = match pos {
+                Some(pos) => {
+                    self.index = start + pos + 1;
+                    start + pos
+                }
+                None => {
+                    self.count = 0;
+                    self.index = self.end;
+                    self.end
+                }
+            };
+
+            Some(Chunk::from_owner_range(
+                self.owner.clone(),
+                self.raw,
+                start,
+                end,
+            ))
+        }
+    }
+}
+
+pub use split_inclusive::*;
+mod split_inclusive {
+    use super::*;
+
+    /// An iterator that yields [chunks](Chunk) terminated by elements that match a predicate.
+    ///
+    /// The matched element is contained in the end of the preceding chunk.
+    #[derive(Debug)]
+    pub struct SplitInclusive<'a, S, T, P>
+    where
+        S: ChunkOwner<T> + Send + Sync + ?Sized + 'a,
+        T: Send + Sync,
+        P: FnMut(&T) -> bool,
+    {
+        pub(crate) index: usize,
+        pub(crate) end: usize,
+        pub(crate) pred: P,
+        pub(crate) owner: Owner<S>,
+        pub(crate) raw: RawSlice<T>,
+        pub(crate) _phantom: PhantomData<&'a T>,
+    }
+
+    impl<'a, S, T, P> SplitInclusive<'a, S, T, P>
+    where
+        S: ChunkOwner<T> + Send + Sync + ?Sized + 'a,
+        T: Send + Sync,
+        P: FnMut(&T) -> bool,
+    {
+        impl_owner_methods!(index, end, pred, raw);
+    }
+
+    impl<'a, S, T, P> Iterator for SplitInclusive<'a, S, T, P>
+    where
+        S: ChunkOwner<T> + Send + Sync + ?Sized + 'a,
+        T: Send + Sync,
+        P: FnMut(&T) -> bool,
+    {
+        type Item = Chunk<'a, S, T>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            if self.index >= self.end {
+                return None;
+            }
+
+            let start = self.index;
+            let pos = unsafe {
+                let slice: &[T] = self.raw.slice(start, self.end);
+                slice.iter().position(&mut self.pred)
+            };
+
+            let end = match pos {
+                Some(pos) => start + pos + 1,
+                None => self.end,
+            };
+            self.index = end;
+
+            Some(Chunk::from_owner_range(
+                self.owner.clone(),
+                self.raw,
+                start,
+                end,
+            ))
+        }
+    }
+}
+
+pub use chunk_by::*;
+mod chunk_by {
+    use super::*;
+
+    /// An iterator that yields [chunks](Chunk) of maximal runs of elements where a predicate
+    /// holds for each consecutive pair.
+    #[derive(Debug)]
+    pub struct ChunkBy<'a, S, T, P>
+    where
+        S: ChunkOwner<T> + Send + Sync + ?Sized + 'a,
+        T: Send + Sync,
+        P: FnMut(&T, &T) -> bool,
+    {
+        pub(crate) index: usize,
+        pub(crate) end: usize,
+        pub(crate) pred: P,
+        pub(crate) owner: Owner<S>,
+        pub(crate) raw: RawSlice<T>,
+        pub(crate) _phantom: PhantomData<&'a T>,
+    }
+
+    impl<'a, S, T, P> ChunkBy<'a, S, T, P>
+    where
+        S: ChunkOwner<T> + Send + Sync + ?Sized + 'a,
+        T: Send + Sync,
+        P: FnMut(&T, &T) -> bool,
+    {
+        impl_owner_methods!(index, end, pred, raw);
+    }
+
+    impl<'a, S, T, P> Iterator for ChunkBy<'a, S, T, P>
+    where
+        S: ChunkOwner<T> + Send + Sync + ?Sized + 'a,
+        T: Send + Sync,
+        P: FnMut(&T, &T) -> bool,
+    {
+        type Item = Chunk<'a, S, T>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            if self.index >= self.end {
+                return None;
+            }
+
+            let start = self.index;
+            let len = unsafe {
+                let slice: &[T] = self.raw.slice(start, self.end);
+                let pred = &mut self.pred;
+                1 + slice
+                    .windows(2)
+                    .take_while(|pair| pred(&pair[0], &pair[1]))
+                    .count()
+            };
+            let end = start + len;
+            self.index = end;
+
+            Some(Chunk::from_owner_range(
+                self.owner.clone(),
+                self.raw,
+                start,
+                end,
+            ))
         }
     }
 }
@@ -197,9 +637,10 @@ mod iter {
     #[derive(Debug)]
     pub struct Iter<'a, S, T>
     where
-        S: Sync + Send + AsRef<[T]> + 'a,
+        S: Sync + Send + ChunkOwner<T> + ?Sized + 'a,
     {
-        pub(crate) owner: Arc<S>,
+        pub(crate) owner: Owner<S>,
+        pub(crate) raw: RawSlice<T>,
         pub(crate) index: usize,
         pub(crate) end: usize,
         pub(crate) _phantom: PhantomData<&'a T>,
@@ -207,15 +648,28 @@ mod iter {
 
     impl<'a, S, T> Iter<'a, S, T>
     where
-        S: Sync + Send + AsRef<[T]> + 'a,
+        S: Sync + Send + ChunkOwner<T> + ?Sized + 'a,
     {
-        pub fn try_unwrap_owner(self) -> Result<S, Self> {
+        /// Returns an iterator that yields each element along with its index in the owner.
+        pub fn enumerate_global(self) -> EnumerateGlobal<'a, S, T> {
+            EnumerateGlobal { iter: self }
+        }
+
+        pub fn try_unwrap_owner(self) -> Result<S, Self>
+        where
+            S: Sized,
+        {
             let Self {
-                owner, index, end, ..
+                owner,
+                raw,
+                index,
+                end,
+                ..
             } = self;
 
-            Arc::try_unwrap(owner).map_err(|owner| Self {
+            owner.try_unwrap().map_err(|owner| Self {
                 owner,
+                raw,
                 index,
                 end,
                 _phantom: PhantomData,
@@ -225,7 +679,7 @@ mod iter {
 
     impl<'a, S, T> Iterator for Iter<'a, S, T>
     where
-        S: Sync + Send + AsRef<[T]> + 'a,
+        S: Sync + Send + ChunkOwner<T> + ?Sized + 'a,
     {
         type Item = Owned<S, T>;
 
@@ -235,10 +689,7 @@ mod iter {
                     return None;
                 }
 
-                let owner_ptr = Arc::as_ptr(&self.owner);
-                let slice: &[T] = owner_ptr.as_ref().unwrap().as_ref();
-                let value = &slice[self.index];
-                let ptr = NonNull::new_unchecked(value as *const T as *mut T);
+                let ptr = self.raw.get(self.index);
                 self.index += 1;
 
                 Some(Owned {
@@ -254,32 +705,78 @@ mod iter {
         }
     }
 
-    impl<'a, S, T> ExactSizeIterator for Iter<'a, S, T> where S: Sync + Send + AsRef<[T]> + 'a {}
+    impl<'a, S, T> ExactSizeIterator for Iter<'a, S, T> where
+        S: Sync + Send + ChunkOwner<T> + ?Sized + 'a
+    {
+    }
+
+    /// The iterator returned from [enumerate_global()](Iter::enumerate_global).
+    #[derive(Debug)]
+    pub struct EnumerateGlobal<'a, S, T>
+    where
+        S: Sync + Send + ChunkOwner<T> + ?Sized + 'a,
+    {
+        pub(crate) iter: Iter<'a, S, T>,
+    }
+
+    impl<'a, S, T> EnumerateGlobal<'a, S, T>
+    where
+        S: Sync + Send + ChunkOwner<T> + ?Sized + 'a,
+    {
+        pub fn into_inner(self) -> Iter<'a, S, T> {
+            self.iter
+        }
+    }
+
+    impl<'a, S, T> Iterator for EnumerateGlobal<'a, S, T>
+    where
+        S: Sync + Send + ChunkOwner<T> + ?Sized + 'a,
+    {
+        type Item = (usize, Owned<S, T>);
+
+        fn next(&mut self) -> Option<Self::Item> {
+            let index = self.iter.index;
+            let elem = self.iter.next()?;
+            Some((index, elem))
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            self.iter.size_hint()
+        }
+    }
+
+    impl<'a, S, T> ExactSizeIterator for EnumerateGlobal<'a, S, T> where
+        S: Sync + Send + ChunkOwner<T> + ?Sized + 'a
+    {
+    }
 }
 
 pub use owned::*;
 pub mod owned {
     use super::*;
 
-    pub struct Owned<S, T> {
-        pub(crate) owner: Arc<S>,
+    pub struct Owned<S: ?Sized, T> {
+        pub(crate) owner: Owner<S>,
         pub(crate) ptr: NonNull<T>,
     }
 
-    impl<S, T> Owned<S, T> {
-        pub fn try_unwrap_owner(self) -> Result<S, Self> {
+    impl<S: ?Sized, T> Owned<S, T> {
+        pub fn try_unwrap_owner(self) -> Result<S, Self>
+        where
+            S: Sized,
+        {
             let Self { owner, ptr } = self;
-            Arc::try_unwrap(owner).map_err(|owner| Self { owner, ptr })
+            owner.try_unwrap().map_err(|owner| Self { owner, ptr })
         }
     }
 
-    impl<S, T> AsRef<T> for Owned<S, T> {
+    impl<S: ?Sized, T> AsRef<T> for Owned<S, T> {
         fn as_ref(&self) -> &T {
             unsafe { self.ptr.as_ref() }
         }
     }
 
-    impl<S, T> Deref for Owned<S, T> {
+    impl<S: ?Sized, T> Deref for Owned<S, T> {
         type Target = T;
 
         fn deref(&self) -> &Self::Target {
@@ -287,7 +784,7 @@ pub mod owned {
         }
     }
 
-    impl<S, T> Debug for Owned<S, T>
+    impl<S: ?Sized, T> Debug for Owned<S, T>
     where
         T: Debug,
     {
@@ -296,7 +793,7 @@ pub mod owned {
         }
     }
 
-    impl<S, T> PartialEq for Owned<S, T>
+    impl<S: ?Sized, T> PartialEq for Owned<S, T>
     where
         T: PartialEq,
     {
@@ -305,9 +802,9 @@ pub mod owned {
         }
     }
 
-    impl<S, T> Eq for Owned<S, T> where T: Eq {}
+    impl<S: ?Sized, T> Eq for Owned<S, T> where T: Eq {}
 
-    impl<S, T> PartialOrd for Owned<S, T>
+    impl<S: ?Sized, T> PartialOrd for Owned<S, T>
     where
         T: PartialOrd,
     {
@@ -316,7 +813,7 @@ pub mod owned {
         }
     }
 
-    impl<S, T> Ord for Owned<S, T>
+    impl<S: ?Sized, T> Ord for Owned<S, T>
     where
         T: Ord,
     {
@@ -325,7 +822,7 @@ pub mod owned {
         }
     }
 
-    impl<S, T> Hash for Owned<S, T>
+    impl<S: ?Sized, T> Hash for Owned<S, T>
     where
         T: Hash,
     {
@@ -337,16 +834,17 @@ pub mod owned {
 
 pub use windows::*;
 mod windows {
-    use crate::{common::*, Chunk};
+    use crate::{common::*, owner::Owner, raw::RawSlice, Chunk};
 
     /// The iterator returned from [owning_windows()](crate::slice::ConcurrentSlice::owning_windows).
     #[derive(Debug)]
     pub struct Windows<'a, S, T>
     where
-        S: AsRef<[T]> + Send + Sync + 'a,
+        S: ChunkOwner<T> + Send + Sync + ?Sized + 'a,
         T: Send + Sync,
     {
-        pub(crate) owner: Arc<S>,
+        pub(crate) owner: Owner<S>,
+        pub(crate) raw: RawSlice<T>,
         pub(crate) size: usize,
         pub(crate) index: usize,
         pub(crate) end: usize,
@@ -355,7 +853,7 @@ mod windows {
 
     impl<'a, S, T> Clone for Windows<'a, S, T>
     where
-        S: AsRef<[T]> + Send + Sync + 'a,
+        S: ChunkOwner<T> + Send + Sync + ?Sized + 'a,
         T: Send + Sync,
     {
         fn clone(&self) -> Self {
@@ -368,40 +866,40 @@ mod windows {
 
     impl<'a, S, T> Iterator for Windows<'a, S, T>
     where
-        S: AsRef<[T]> + Send + Sync + 'a,
+        S: ChunkOwner<T> + Send + Sync + ?Sized + 'a,
         T: Send + Sync,
     {
         type Item = Chunk<'a, S, T>;
 
         fn next(&mut self) -> Option<Self::Item> {
-            unsafe {
-                let rear = self.index + self.size;
-
-                if rear > self.end {
-                    return None;
-                }
-
-                let slice: &[T] = &self.owner.as_ref().as_ref()[self.index..rear];
-                let slice_ptr = NonNull::new_unchecked(slice as *const [T] as *mut [T]);
-                self.index += 1;
+            let rear = match self.index.checked_add(self.size) {
+                Some(rear) if rear <= self.end => rear,
+                _ => return None,
+            };
 
-                Some(Chunk {
-                    owner: self.owner.clone(),
-                    slice: slice_ptr,
-                    _phantom: PhantomData,
-                })
-            }
+            let start = self.index;
+            self.index += 1;
+
+            Some(Chunk::from_owner_range(
+                self.owner.clone(),
+                self.raw,
+                start,
+                rear,
+            ))
         }
 
         fn size_hint(&self) -> (usize, Option<usize>) {
-            let len = self.end - (self.index + self.size);
+            let len = match self.index.checked_add(self.size) {
+                Some(rear) if rear <= self.end => (self.end - rear).saturating_add(1),
+                _ => 0,
+            };
             (len, Some(len))
         }
     }
 
     impl<'a, S, T> ExactSizeIterator for Windows<'a, S, T>
     where
-        S: AsRef<[T]> + Send + Sync + 'a,
+        S: ChunkOwner<T> + Send + Sync + ?Sized + 'a,
         T: Send + Sync,
     {
     }
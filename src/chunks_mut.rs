@@ -1,4 +1,68 @@
-use crate::{chunk_mut::ChunkMut, common::*};
+use crate::{
+    chunk_mut::ChunkMut, common::*, error::TryUnwrapResult, owner::Owner,
+    partition::PartitionCounter, raw::RawSlice,
+};
+
+/// Implements the methods to access the owner on a mutable chunk iterator, given the fields
+/// other than `owner` and `_phantom` to rebuild the iterator with.
+macro_rules! impl_owner_methods {
+    ($($field:ident),* $(,)?) => {
+        /// Converts to the reference-counted owner.
+        ///
+        /// The method succeeds if no chunk or other iterator refers to the owner, which
+        /// could otherwise mutate the data behind the returned [Arc]. Otherwise, it returns
+        /// the iterator intact.
+        pub fn into_arc_owner(self) -> TryUnwrapResult<Arc<S>, Self> {
+            let Self { owner, $($field,)* .. } = self;
+
+            owner.try_recover_arc().map_err(|err| {
+                err.map_shared(|owner| Self {
+                    owner,
+                    $($field,)*
+                    _phantom: PhantomData,
+                })
+            })
+        }
+
+        /// Tries to recover the owning data.
+        ///
+        /// The method succeeds if the referencing chunk iterator and all chunks are dropped.
+        /// Otherwise, it returns the iterator intact.
+        pub fn try_unwrap_owner(self) -> TryUnwrapResult<S, Self>
+        where
+            S: Sized,
+        {
+            let Self { owner, $($field,)* .. } = self;
+
+            owner.try_recover().map_err(|err| {
+                err.map_shared(|owner| Self {
+                    owner,
+                    $($field,)*
+                    _phantom: PhantomData,
+                })
+            })
+        }
+
+        /// Gets the reference count on the owning data.
+        pub fn ref_count(&self) -> usize {
+            self.owner.ref_count()
+        }
+
+        /// Lists the chunks referring to the owning data, which are still alive.
+        ///
+        /// Chunk iterators and owned elements referring to the data are not listed.
+        #[cfg(feature = "tracking")]
+        pub fn live_chunks(&self) -> Vec<ChunkInfo> {
+            self.owner.live_chunks()
+        }
+
+        /// Returns an iterator that stops yielding chunks once the owning data is cancelled
+        /// by [cancel()](ChunkMut::cancel).
+        pub fn cancellable(self) -> Cancellable<Self> {
+            Cancellable::new(self.owner.state().clone(), self)
+        }
+    };
+}
 
 pub use sized_chunks_mut::*;
 mod sized_chunks_mut {
@@ -8,56 +72,29 @@ mod sized_chunks_mut {
     #[derive(Debug)]
     pub struct SizedChunksMut<'a, S, T>
     where
-        S: AsMut<[T]> + Send + Sync + 'a,
+        S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
         T: Send + Sync,
     {
         pub(crate) index: usize,
         pub(crate) chunk_size: usize,
         pub(crate) end: usize,
-        pub(crate) owner: Arc<S>,
+        pub(crate) partition: PartitionCounter,
+        pub(crate) owner: Owner<S>,
+        pub(crate) raw: RawSlice<T>,
         pub(crate) _phantom: PhantomData<&'a T>,
     }
 
     impl<'a, S, T> SizedChunksMut<'a, S, T>
     where
-        S: AsMut<[T]> + Send + Sync + 'a,
+        S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
         T: Send + Sync,
     {
-        pub fn into_arc_owner(self) -> Arc<S> {
-            self.owner
-        }
-
-        /// Tries to recover the owning data.
-        ///
-        /// The method succeeds if the referencing chunk iterator and all chunks are dropped.
-        /// Otherwise, it returns the guard intact.
-        pub fn try_unwrap_owner(self) -> Result<S, Self> {
-            let Self {
-                index,
-                chunk_size,
-                end,
-                owner,
-                ..
-            } = self;
-
-            Arc::try_unwrap(owner).map_err(|owner| Self {
-                index,
-                chunk_size,
-                end,
-                owner,
-                _phantom: PhantomData,
-            })
-        }
-
-        /// Gets the reference count on the owning data.
-        pub fn ref_count(&self) -> usize {
-            Arc::strong_count(&self.owner)
-        }
+        impl_owner_methods!(index, chunk_size, end, partition, raw);
     }
 
     impl<'a, S, T> Iterator for SizedChunksMut<'a, S, T>
     where
-        S: AsMut<[T]> + Send + Sync + 'a,
+        S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
         T: Send + Sync,
     {
         type Item = ChunkMut<'a, S, T>;
@@ -68,22 +105,15 @@ mod sized_chunks_mut {
             }
 
             let start = self.index;
-            let end = cmp::min(start + self.chunk_size, self.end);
+            let end = start + cmp::min(self.chunk_size, self.end - start);
             self.index = end;
 
-            let owner = self.owner.clone();
+            let mut chunk =
+                unsafe { ChunkMut::from_owner_range(self.owner.clone(), self.raw, start, end) };
 
-            let slice = unsafe {
-                let ptr = Arc::as_ptr(&owner) as *mut S;
-                let slice: &mut [T] = ptr.as_mut().unwrap().as_mut();
-                NonNull::new_unchecked(&mut slice[start..end] as *mut [T])
-            };
+            chunk.partition = Some(self.partition.next_partition());
 
-            Some(ChunkMut {
-                owner,
-                slice,
-                _phantom: PhantomData,
-            })
+            Some(chunk)
         }
     }
 }
@@ -96,59 +126,30 @@ mod even_chunks_mut {
     #[derive(Debug)]
     pub struct EvenChunksMut<'a, S, T>
     where
-        S: AsMut<[T]> + Send + Sync + 'a,
+        S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
         T: Send + Sync,
     {
         pub(crate) base_chunk_size: usize,
         pub(crate) index: usize,
         pub(crate) long_end: usize,
         pub(crate) short_end: usize,
-        pub(crate) owner: Arc<S>,
+        pub(crate) partition: PartitionCounter,
+        pub(crate) owner: Owner<S>,
+        pub(crate) raw: RawSlice<T>,
         pub(crate) _phantom: PhantomData<&'a T>,
     }
 
     impl<'a, S, T> EvenChunksMut<'a, S, T>
     where
-        S: AsMut<[T]> + Send + Sync + 'a,
+        S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
         T: Send + Sync,
     {
-        pub fn into_arc_owner(self) -> Arc<S> {
-            self.owner
-        }
-
-        /// Tries to recover the owning data.
-        ///
-        /// The method succeeds if the referencing chunk iterator and all chunks are dropped.
-        /// Otherwise, it returns the guard intact.
-        pub fn try_unwrap_owner(self) -> Result<S, Self> {
-            let Self {
-                index,
-                base_chunk_size,
-                long_end,
-                short_end,
-                owner,
-                ..
-            } = self;
-
-            Arc::try_unwrap(owner).map_err(|owner| Self {
-                index,
-                base_chunk_size,
-                long_end,
-                short_end,
-                owner,
-                _phantom: PhantomData,
-            })
-        }
-
-        /// Gets the reference count on the owning data.
-        pub fn ref_count(&self) -> usize {
-            Arc::strong_count(&self.owner)
-        }
+        impl_owner_methods!(index, base_chunk_size, long_end, short_end, partition, raw);
     }
 
     impl<'a, S, T> Iterator for EvenChunksMut<'a, S, T>
     where
-        S: AsMut<[T]> + Send + Sync + 'a,
+        S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
         T: Send + Sync,
     {
         type Item = ChunkMut<'a, S, T>;
@@ -173,18 +174,458 @@ mod even_chunks_mut {
                     || (start < self.short_end && end <= self.short_end)
             );
 
-            let owner = self.owner.clone();
-            let slice = unsafe {
-                let ptr = Arc::as_ptr(&owner) as *mut S;
-                let slice: &mut [T] = ptr.as_mut().unwrap().as_mut();
-                NonNull::new_unchecked(&mut slice[start..end] as *mut [T])
-            };
+            let mut chunk =
+                unsafe { ChunkMut::from_owner_range(self.owner.clone(), self.raw, start, end) };
 
-            Some(ChunkMut {
+            chunk.partition = Some(self.partition.next_partition());
+
+            Some(chunk)
+        }
+    }
+}
+
+pub use exact_chunks_mut::*;
+mod exact_chunks_mut {
+    use super::*;
+
+    /// An iterator that yields [chunks](ChunkMut) of exactly the same size.
+    #[derive(Debug)]
+    pub struct ExactChunksMut<'a, S, T>
+    where
+        S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
+        T: Send + Sync,
+    {
+        pub(crate) index: usize,
+        pub(crate) chunk_size: usize,
+        pub(crate) end: usize,
+        pub(crate) rem_end: usize,
+        pub(crate) partition: PartitionCounter,
+        pub(crate) owner: Owner<S>,
+        pub(crate) raw: RawSlice<T>,
+        pub(crate) _phantom: PhantomData<&'a T>,
+    }
+
+    impl<'a, S, T> ExactChunksMut<'a, S, T>
+    where
+        S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
+        T: Send + Sync,
+    {
+        /// Returns the trailing elements that do not fill a whole chunk.
+        ///
+        /// The chunks not yet yielded by the iterator are discarded.
+        pub fn into_remainder(self) -> ChunkMut<'a, S, T> {
+            let Self {
+                end,
+                rem_end,
                 owner,
-                slice,
-                _phantom: PhantomData,
-            })
+                raw,
+                ..
+            } = self;
+            unsafe { ChunkMut::from_owner_range(owner, raw, end, rem_end) }
+        }
+
+        impl_owner_methods!(index, chunk_size, end, rem_end, partition, raw);
+    }
+
+    impl<'a, S, T> Iterator for ExactChunksMut<'a, S, T>
+    where
+        S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
+        T: Send + Sync,
+    {
+        type Item = ChunkMut<'a, S, T>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            if self.index >= self.end {
+                return None;
+            }
+
+            let start = self.index;
+            let end = start + self.chunk_size;
+            self.index = end;
+
+            let mut chunk =
+                unsafe { ChunkMut::from_owner_range(self.owner.clone(), self.raw, start, end) };
+            chunk.partition = Some(self.partition.next_partition());
+            Some(chunk)
+        }
+    }
+}
+
+pub use rchunks_mut::*;
+mod rchunks_mut {
+    use super::*;
+
+    /// An iterator that yields [chunks](ChunkMut) starting from the end of the slice.
+    #[derive(Debug)]
+    pub struct RChunksMut<'a, S, T>
+    where
+        S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
+        T: Send + Sync,
+    {
+        pub(crate) start: usize,
+        pub(crate) chunk_size: usize,
+        pub(crate) index: usize,
+        pub(crate) partition: PartitionCounter,
+        pub(crate) owner: Owner<S>,
+        pub(crate) raw: RawSlice<T>,
+        pub(crate) _phantom: PhantomData<&'a T>,
+    }
+
+    impl<'a, S, T> RChunksMut<'a, S, T>
+    where
+        S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
+        T: Send + Sync,
+    {
+        impl_owner_methods!(start, chunk_size, index, partition, raw);
+    }
+
+    impl<'a, S, T> Iterator for RChunksMut<'a, S, T>
+    where
+        S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
+        T: Send + Sync,
+    {
+        type Item = ChunkMut<'a, S, T>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            if self.index <= self.start {
+                return None;
+            }
+
+            let end = self.index;
+            let start = cmp::max(self.start, end.saturating_sub(self.chunk_size));
+            self.index = start;
+
+            let mut chunk =
+                unsafe { ChunkMut::from_owner_range(self.owner.clone(), self.raw, start, end) };
+            chunk.partition = Some(self.partition.next_partition());
+            Some(chunk)
+        }
+    }
+}
+
+pub use rchunks_exact_mut::*;
+mod rchunks_exact_mut {
+    use super::*;
+
+    /// An iterator that yields [chunks](ChunkMut) of exactly the same size starting from the end of
+    /// the slice.
+    #[derive(Debug)]
+    pub struct RChunksExactMut<'a, S, T>
+    where
+        S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
+        T: Send + Sync,
+    {
+        pub(crate) rem_start: usize,
+        pub(crate) start: usize,
+        pub(crate) chunk_size: usize,
+        pub(crate) index: usize,
+        pub(crate) partition: PartitionCounter,
+        pub(crate) owner: Owner<S>,
+        pub(crate) raw: RawSlice<T>,
+        pub(crate) _phantom: PhantomData<&'a T>,
+    }
+
+    impl<'a, S, T> RChunksExactMut<'a, S, T>
+    where
+        S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
+        T: Send + Sync,
+    {
+        /// Returns the leading elements that do not fill a whole chunk.
+        ///
+        /// The chunks not yet yielded by the iterator are discarded.
+        pub fn into_remainder(self) -> ChunkMut<'a, S, T> {
+            let Self {
+                rem_start,
+                start,
+                owner,
+                raw,
+                ..
+            } = self;
+            unsafe { ChunkMut::from_owner_range(owner, raw, rem_start, start) }
+        }
+
+        impl_owner_methods!(rem_start, start, chunk_size, index, partition, raw);
+    }
+
+    impl<'a, S, T> Iterator for RChunksExactMut<'a, S, T>
+    where
+        S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
+        T: Send + Sync,
+    {
+        type Item = ChunkMut<'a, S, T>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            if self.index <= self.start {
+                return None;
+            }
+
+            let end = self.index;
+            let start = end - self.chunk_size;
+            self.index = start;
+
+            let mut chunk =
+                unsafe { ChunkMut::from_owner_range(self.owner.clone(), self.raw, start, end) };
+            chunk.partition = Some(self.partition.next_partition());
+            Some(chunk)
+        }
+    }
+}
+
+pub use split_mut::*;
+mod split_mut {
+    use super::*;
+
+    /// An iterator that yields [chunks](ChunkMut) separated by elements that match a predicate.
+    #[derive(Debug)]
+    pub struct SplitMut<'a, S, T, P>
+    where
+        S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
+        T: Send + Sync,
+        P: FnMut(&T) -> bool,
+    {
+        pub(crate) index: usize,
+        pub(crate) end: usize,
+        pub(crate) finished: bool,
+        pub(crate) pred: P,
+        pub(crate) owner: Owner<S>,
+        pub(crate) raw: RawSlice<T>,
+        pub(crate) _phantom: PhantomData<&'a T>,
+    }
+
+    impl<'a, S, T, P> SplitMut<'a, S, T, P>
+    where
+        S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
+        T: Send + Sync,
+        P: FnMut(&T) -> bool,
+    {
+        impl_owner_methods!(index, end, finished, pred, raw);
+    }
+
+    impl<'a, S, T, P> Iterator for SplitMut<'a, S, T, P>
+    where
+        S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
+        T: Send + Sync,
+        P: FnMut(&T) -> bool,
+    {
+        type Item = ChunkMut<'a, S, T>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            if self.finished {
+                return None;
+            }
+
+            let start = self.index;
+            let pos = unsafe {
+                let slice: &[T] = self.raw.slice(start, self.end);
+                slice.iter().position(&mut self.pred)
+            };
+
+            let end = match pos {
+                Some(pos) => {
+                    self.index = start + pos + 1;
+                    start + pos
+                }
+                None => {
+                    self.finished = true;
+                    self.index = self.end;
+                    self.end
+                }
+            };
+
+            Some(unsafe { ChunkMut::from_owner_range(self.owner.clone(), self.raw, start, end) })
+        }
+    }
+}
+
+pub use splitn_mut::*;
+mod splitn_mut {
+    use super::*;
+
+    /// An iterator that yields at most a limited number of [chunks](ChunkMut) separated by elements
+    /// that match a predicate.
+    #[derive(Debug)]
+    pub struct SplitNMut<'a, S, T, P>
+    where
+        S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
+        T: Send + Sync,
+        P: FnMut(&T) -> bool,
+    {
+        pub(crate) index: usize,
+        pub(crate) end: usize,
+        pub(crate) count: usize,
+        pub(crate) pred: P,
+        pub(crate) owner: Owner<S>,
+        pub(crate) raw: RawSlice<T>,
+        pub(crate) _phantom: PhantomData<&'a T>,
+    }
+
+    impl<'a, S, T, P> SplitNMut<'a, S, T, P>
+    where
+        S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
+        T: Send + Sync,
+        P: FnMut(&T) -> bool,
+    {
+        impl_owner_methods!(index, end, count, pred, raw);
+    }
+
+    impl<'a, S, T, P> Iterator for SplitNMut<'a, S, T, P>
+    where
+        S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
+        T: Send + Sync,
+        P: FnMut(&T) -> bool,
+    {
+        type Item = ChunkMut<'a, S, T>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            if self.count == 0 {
+                return None;
+            }
+            self.count -= 1;
+
+            let start = self.index;
+            let pos = if self.count == 0 {
+                None
+            } else {
+                unsafe {
+                    let slice: &[T] = self.raw.slice(start, self.end);
+                    slice.iter().position(&mut self.pred)
+                }
+            };
+
+            let end = match pos {
+                Some(pos) => {
+                    self.index = start + pos + 1;
+                    start + pos
+                }
+                None => {
+                    self.count = 0;
+                    self.index = self.end;
+                    self.end
+                }
+            };
+
+            Some(unsafe { ChunkMut::from_owner_range(self.owner.clone(), self.raw, start, end) })
+        }
+    }
+}
+
+pub use split_inclusive_mut::*;
+mod split_inclusive_mut {
+    use super::*;
+
+    /// An iterator that yields [chunks](ChunkMut) terminated by elements that match a predicate.
+    ///
+    /// The matched element is contained in the end of the preceding chunk.
+    #[derive(Debug)]
+    pub struct SplitInclusiveMut<'a, S, T, P>
+    where
+        S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
+        T: Send + Sync,
+        P: FnMut(&T) -> bool,
+    {
+        pub(crate) index: usize,
+        pub(crate) end: usize,
+        pub(crate) pred: P,
+        pub(crate) owner: Owner<S>,
+        pub(crate) raw: RawSlice<T>,
+        pub(crate) _phantom: PhantomData<&'a T>,
+    }
+
+    impl<'a, S, T, P> SplitInclusiveMut<'a, S, T, P>
+    where
+        S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
+        T: Send + Sync,
+        P: FnMut(&T) -> bool,
+    {
+        impl_owner_methods!(index, end, pred, raw);
+    }
+
+    impl<'a, S, T, P> Iterator for SplitInclusiveMut<'a, S, T, P>
+    where
+        S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
+        T: Send + Sync,
+        P: FnMut(&T) -> bool,
+    {
+        type Item = ChunkMut<'a, S, T>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            if self.index >= self.end {
+                return None;
+            }
+
+            let start = self.index;
+            let pos = unsafe {
+                let slice: &[T] = self.raw.slice(start, self.end);
+                slice.iter().position(&mut self.pred)
+            };
+
+            let end = match pos {
+                Some(pos) => start + pos + 1,
+                None => self.end,
+            };
+            self.index = end;
+
+            Some(unsafe { ChunkMut::from_owner_range(self.owner.clone(), self.raw, start, end) })
+        }
+    }
+}
+
+pub use chunk_by_mut::*;
+mod chunk_by_mut {
+    use super::*;
+
+    /// An iterator that yields [chunks](ChunkMut) of maximal runs of elements where a predicate
+    /// holds for each consecutive pair.
+    #[derive(Debug)]
+    pub struct ChunkByMut<'a, S, T, P>
+    where
+        S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
+        T: Send + Sync,
+        P: FnMut(&T, &T) -> bool,
+    {
+        pub(crate) index: usize,
+        pub(crate) end: usize,
+        pub(crate) pred: P,
+        pub(crate) owner: Owner<S>,
+        pub(crate) raw: RawSlice<T>,
+        pub(crate) _phantom: PhantomData<&'a T>,
+    }
+
+    impl<'a, S, T, P> ChunkByMut<'a, S, T, P>
+    where
+        S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
+        T: Send + Sync,
+        P: FnMut(&T, &T) -> bool,
+    {
+        impl_owner_methods!(index, end, pred, raw);
+    }
+
+    impl<'a, S, T, P> Iterator for ChunkByMut<'a, S, T, P>
+    where
+        S: ChunkOwnerMut<T> + Send + Sync + ?Sized + 'a,
+        T: Send + Sync,
+        P: FnMut(&T, &T) -> bool,
+    {
+        type Item = ChunkMut<'a, S, T>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            if self.index >= self.end {
+                return None;
+            }
+
+            let start = self.index;
+            let len = unsafe {
+                let slice: &[T] = self.raw.slice(start, self.end);
+                let pred = &mut self.pred;
+                1 + slice
+                    .windows(2)
+                    .take_while(|pair| pred(&pair[0], &pair[1]))
+                    .count()
+            };
+            let end = start + len;
+            self.index = end;
+
+            Some(unsafe { ChunkMut::from_owner_range(self.owner.clone(), self.raw, start, end) })
         }
     }
 }
@@ -197,9 +638,10 @@ mod iter_mut {
     #[derive(Debug)]
     pub struct IterMut<'a, S, T>
     where
-        S: Sync + Send + AsMut<[T]> + 'a,
+        S: Sync + Send + ChunkOwnerMut<T> + ?Sized + 'a,
     {
-        pub(crate) owner: Arc<S>,
+        pub(crate) owner: Owner<S>,
+        pub(crate) raw: RawSlice<T>,
         pub(crate) index: usize,
         pub(crate) end: usize,
         pub(crate) _phantom: PhantomData<&'a T>,
@@ -207,25 +649,40 @@ mod iter_mut {
 
     impl<'a, S, T> IterMut<'a, S, T>
     where
-        S: Sync + Send + AsMut<[T]> + 'a,
+        S: Sync + Send + ChunkOwnerMut<T> + ?Sized + 'a,
     {
-        pub fn try_unwrap_owner(self) -> Result<S, Self> {
-            let Self {
-                owner, index, end, ..
-            } = self;
+        /// Returns an iterator that yields each element along with its index in the owner.
+        pub fn enumerate_global(self) -> EnumerateGlobalMut<'a, S, T> {
+            EnumerateGlobalMut { iter: self }
+        }
 
-            Arc::try_unwrap(owner).map_err(|owner| Self {
+        pub fn try_unwrap_owner(self) -> TryUnwrapResult<S, Self>
+        where
+            S: Sized,
+        {
+            let Self {
                 owner,
+                raw,
                 index,
                 end,
-                _phantom: PhantomData,
+                ..
+            } = self;
+
+            owner.try_recover().map_err(|err| {
+                err.map_shared(|owner| Self {
+                    owner,
+                    raw,
+                    index,
+                    end,
+                    _phantom: PhantomData,
+                })
             })
         }
     }
 
     impl<'a, S, T> Iterator for IterMut<'a, S, T>
     where
-        S: Sync + Send + AsMut<[T]> + 'a,
+        S: Sync + Send + ChunkOwnerMut<T> + ?Sized + 'a,
     {
         type Item = OwnedMut<S, T>;
 
@@ -235,15 +692,14 @@ mod iter_mut {
                     return None;
                 }
 
-                let owner_ptr = Arc::as_ptr(&self.owner) as *mut S;
-                let slice: &mut [T] = owner_ptr.as_mut().unwrap().as_mut();
-                let value = &mut slice[self.index];
-                let ptr = NonNull::new_unchecked(value as *mut T);
+                let ptr = self.raw.get(self.index);
                 self.index += 1;
 
                 Some(OwnedMut {
                     owner: self.owner.clone(),
                     ptr,
+                    start: self.index - 1,
+                    end: self.index,
                 })
             }
         }
@@ -254,38 +710,187 @@ mod iter_mut {
         }
     }
 
-    impl<'a, S, T> ExactSizeIterator for IterMut<'a, S, T> where S: Sync + Send + AsMut<[T]> + 'a {}
+    impl<'a, S, T> ExactSizeIterator for IterMut<'a, S, T> where
+        S: Sync + Send + ChunkOwnerMut<T> + ?Sized + 'a
+    {
+    }
+
+    /// The iterator returned from [enumerate_global()](IterMut::enumerate_global).
+    #[derive(Debug)]
+    pub struct EnumerateGlobalMut<'a, S, T>
+    where
+        S: Sync + Send + ChunkOwnerMut<T> + ?Sized + 'a,
+    {
+        pub(crate) iter: IterMut<'a, S, T>,
+    }
+
+    impl<'a, S, T> EnumerateGlobalMut<'a, S, T>
+    where
+        S: Sync + Send + ChunkOwnerMut<T> + ?Sized + 'a,
+    {
+        pub fn into_inner(self) -> IterMut<'a, S, T> {
+            self.iter
+        }
+    }
+
+    impl<'a, S, T> Iterator for EnumerateGlobalMut<'a, S, T>
+    where
+        S: Sync + Send + ChunkOwnerMut<T> + ?Sized + 'a,
+    {
+        type Item = (usize, OwnedMut<S, T>);
+
+        fn next(&mut self) -> Option<Self::Item> {
+            let index = self.iter.index;
+            let elem = self.iter.next()?;
+            Some((index, elem))
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            self.iter.size_hint()
+        }
+    }
+
+    impl<'a, S, T> ExactSizeIterator for EnumerateGlobalMut<'a, S, T> where
+        S: Sync + Send + ChunkOwnerMut<T> + ?Sized + 'a
+    {
+    }
+}
+
+pub use array_iter_mut::*;
+mod array_iter_mut {
+    use super::*;
+
+    /// The iterator returned from [into_iter_owned()](crate::ArrayChunkMut::into_iter_owned).
+    #[derive(Debug)]
+    pub struct ArrayIterMut<'a, S, T, const N: usize>
+    where
+        S: Sync + Send + ChunkOwnerMut<T> + ?Sized + 'a,
+    {
+        pub(crate) owner: Owner<S>,
+        pub(crate) raw: RawSlice<T>,
+        pub(crate) index: usize,
+        pub(crate) end: usize,
+        pub(crate) _phantom: PhantomData<&'a T>,
+    }
+
+    impl<'a, S, T, const N: usize> ArrayIterMut<'a, S, T, N>
+    where
+        S: Sync + Send + ChunkOwnerMut<T> + ?Sized + 'a,
+    {
+        pub fn try_unwrap_owner(self) -> TryUnwrapResult<S, Self>
+        where
+            S: Sized,
+        {
+            let Self {
+                owner,
+                raw,
+                index,
+                end,
+                ..
+            } = self;
+
+            owner.try_recover().map_err(|err| {
+                err.map_shared(|owner| Self {
+                    owner,
+                    raw,
+                    index,
+                    end,
+                    _phantom: PhantomData,
+                })
+            })
+        }
+    }
+
+    impl<'a, S, T, const N: usize> Iterator for ArrayIterMut<'a, S, T, N>
+    where
+        S: Sync + Send + ChunkOwnerMut<T> + ?Sized + 'a,
+    {
+        type Item = OwnedMut<S, [T; N]>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            unsafe {
+                if self.index == self.end {
+                    return None;
+                }
+
+                let ptr = self.raw.get(self.index).cast::<[T; N]>();
+                self.index += N;
+
+                Some(OwnedMut {
+                    owner: self.owner.clone(),
+                    ptr,
+                    start: self.index - N,
+                    end: self.index,
+                })
+            }
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            let len = (self.end - self.index) / N;
+            (len, Some(len))
+        }
+    }
+
+    impl<'a, S, T, const N: usize> ExactSizeIterator for ArrayIterMut<'a, S, T, N> where
+        S: Sync + Send + ChunkOwnerMut<T> + ?Sized + 'a
+    {
+    }
 }
 
 pub use owned_mut::*;
 pub mod owned_mut {
     use super::*;
+    use core::{mem::ManuallyDrop, ptr};
 
-    pub struct OwnedMut<S, T> {
-        pub(crate) owner: Arc<S>,
+    /// An owned reference to an element, which marks the element poisoned on the owner if
+    /// it is dropped during a panic.
+    pub struct OwnedMut<S: ?Sized, T> {
+        pub(crate) owner: Owner<S>,
         pub(crate) ptr: NonNull<T>,
+        pub(crate) start: usize,
+        pub(crate) end: usize,
+    }
+
+    impl<S: ?Sized, T> OwnedMut<S, T> {
+        pub fn try_unwrap_owner(self) -> TryUnwrapResult<S, Self>
+        where
+            S: Sized,
+        {
+            let this = ManuallyDrop::new(self);
+            let (ptr, start, end) = (this.ptr, this.start, this.end);
+
+            // the owner is read exactly once from the forgotten element
+            let owner = unsafe { ptr::read(&this.owner) };
+            owner.try_recover().map_err(|err| {
+                err.map_shared(|owner| Self {
+                    owner,
+                    ptr,
+                    start,
+                    end,
+                })
+            })
+        }
     }
 
-    impl<S, T> OwnedMut<S, T> {
-        pub fn try_unwrap_owner(self) -> Result<S, Self> {
-            let Self { owner, ptr } = self;
-            Arc::try_unwrap(owner).map_err(|owner| Self { owner, ptr })
+    impl<S: ?Sized, T> Drop for OwnedMut<S, T> {
+        fn drop(&mut self) {
+            self.owner.poison_on_panic(self.start..self.end);
         }
     }
 
-    impl<S, T> AsRef<T> for OwnedMut<S, T> {
+    impl<S: ?Sized, T> AsRef<T> for OwnedMut<S, T> {
         fn as_ref(&self) -> &T {
             unsafe { self.ptr.as_ref() }
         }
     }
 
-    impl<S, T> AsMut<T> for OwnedMut<S, T> {
+    impl<S: ?Sized, T> AsMut<T> for OwnedMut<S, T> {
         fn as_mut(&mut self) -> &mut T {
             unsafe { self.ptr.as_mut() }
         }
     }
 
-    impl<S, T> Deref for OwnedMut<S, T> {
+    impl<S: ?Sized, T> Deref for OwnedMut<S, T> {
         type Target = T;
 
         fn deref(&self) -> &Self::Target {
@@ -293,13 +898,13 @@ pub mod owned_mut {
         }
     }
 
-    impl<S, T> DerefMut for OwnedMut<S, T> {
+    impl<S: ?Sized, T> DerefMut for OwnedMut<S, T> {
         fn deref_mut(&mut self) -> &mut Self::Target {
             unsafe { self.ptr.as_mut() }
         }
     }
 
-    impl<S, T> Debug for OwnedMut<S, T>
+    impl<S: ?Sized, T> Debug for OwnedMut<S, T>
     where
         T: Debug,
     {
@@ -308,7 +913,7 @@ pub mod owned_mut {
         }
     }
 
-    impl<S, T> PartialEq for OwnedMut<S, T>
+    impl<S: ?Sized, T> PartialEq for OwnedMut<S, T>
     where
         T: PartialEq,
     {
@@ -317,9 +922,9 @@ pub mod owned_mut {
         }
     }
 
-    impl<S, T> Eq for OwnedMut<S, T> where T: Eq {}
+    impl<S: ?Sized, T> Eq for OwnedMut<S, T> where T: Eq {}
 
-    impl<S, T> PartialOrd for OwnedMut<S, T>
+    impl<S: ?Sized, T> PartialOrd for OwnedMut<S, T>
     where
         T: PartialOrd,
     {
@@ -328,7 +933,7 @@ pub mod owned_mut {
         }
     }
 
-    impl<S, T> Ord for OwnedMut<S, T>
+    impl<S: ?Sized, T> Ord for OwnedMut<S, T>
     where
         T: Ord,
     {
@@ -337,7 +942,7 @@ pub mod owned_mut {
         }
     }
 
-    impl<S, T> Hash for OwnedMut<S, T>
+    impl<S: ?Sized, T> Hash for OwnedMut<S, T>
     where
         T: Hash,
     {
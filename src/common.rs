@@ -1,14 +1,20 @@
-pub use owning_ref::{ArcRef, CloneStableAddress, OwningRef};
-pub use std::{
-    cmp::{self, Ordering::*},
-    fmt,
+pub use crate::cancel::Cancellable;
+pub use crate::chunk_owner::{ChunkOwner, ChunkOwnerMut};
+#[cfg(feature = "tracking")]
+pub use crate::tracking::ChunkInfo;
+pub use alloc::{rc::Rc, sync::Arc, vec, vec::Vec};
+pub use core::{
+    cmp, fmt,
     fmt::Debug,
     hash::{Hash, Hasher},
     iter::{self, ExactSizeIterator},
     marker::PhantomData,
     mem,
     ops::{Deref, DerefMut},
-    ptr::{self, NonNull},
+    ptr::NonNull,
     slice,
-    sync::Arc,
 };
+#[cfg(feature = "std")]
+pub use owning_ref::ArcRef;
+#[cfg(feature = "std")]
+pub use std::thread;
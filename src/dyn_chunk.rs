@@ -0,0 +1,217 @@
+use crate::{
+    chunk::Chunk,
+    chunk_mut::{ChunkMut, ChunkMutParts},
+    common::*,
+    error::{TryUnwrapError, TryUnwrapResult},
+    owner::Owner,
+};
+use core::{any::Any, cell::UnsafeCell};
+
+/// A [Chunk] with the owner type erased.
+///
+/// Chunks backed by different owner types, e.g. [Vec] and [Box], can be stored in
+/// the same collection once converted by [into_dyn()](Chunk::into_dyn).
+pub type DynChunk<T> = Chunk<'static, dyn AnyChunkOwner<T>, T>;
+
+/// A [ChunkMut] with the owner type erased.
+///
+/// Mutable chunks backed by different owner types can be stored in the same collection
+/// once converted by [into_dyn()](ChunkMut::into_dyn).
+pub type DynChunkMut<T> = ChunkMut<'static, dyn AnyChunkOwnerMut<T>, T>;
+
+/// A type-erased owner of [DynChunk], which can be downcast to the concrete type.
+pub trait AnyChunkOwner<T>: ChunkOwner<T> + Any + Send + Sync {}
+
+impl<S, T> AnyChunkOwner<T> for S where S: ChunkOwner<T> + Any + Send + Sync {}
+
+impl<T> Debug for dyn AnyChunkOwner<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AnyChunkOwner").finish_non_exhaustive()
+    }
+}
+
+/// A type-erased owner of [DynChunkMut], which can be downcast to the concrete type.
+pub trait AnyChunkOwnerMut<T>: ChunkOwnerMut<T> + Any + Send + Sync {}
+
+impl<S, T> AnyChunkOwnerMut<T> for S where S: ChunkOwnerMut<T> + Any + Send + Sync {}
+
+impl<T> Debug for dyn AnyChunkOwnerMut<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AnyChunkOwnerMut").finish_non_exhaustive()
+    }
+}
+
+impl<S, T> Chunk<'static, S, T>
+where
+    S: ChunkOwner<T> + Send + Sync + 'static,
+    T: Send + Sync,
+{
+    /// Erases the owner type of the chunk.
+    pub fn into_dyn(self) -> DynChunk<T> {
+        let Self {
+            owner,
+            raw,
+            start,
+            end,
+            partition,
+            tracker,
+            ..
+        } = self;
+
+        Chunk {
+            owner: owner.coerce(|arc| arc as Arc<UnsafeCell<dyn AnyChunkOwner<T>>>),
+            raw,
+            start,
+            end,
+            partition,
+            tracker,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T> DynChunk<T>
+where
+    T: Send + Sync,
+{
+    /// Tries to recover the owning data of type `S`.
+    ///
+    /// The method succeeds if the owner is of type `S`, and the referencing chunk iterator
+    /// and all chunks are dropped. Otherwise, it returns the chunk intact.
+    pub fn try_unwrap_owner<S>(self) -> Result<S, Self>
+    where
+        S: ChunkOwner<T> + Send + Sync + 'static,
+    {
+        let Self {
+            owner,
+            raw,
+            start,
+            end,
+            partition,
+            tracker,
+            ..
+        } = self;
+
+        let rebuild = |owner| Self {
+            owner,
+            raw,
+            start,
+            end,
+            partition,
+            tracker,
+            _phantom: PhantomData,
+        };
+
+        let owner = match try_cast_as::<_, S>(owner, |data: &dyn AnyChunkOwner<T>| data as &dyn Any)
+        {
+            Ok(owner) => owner,
+            Err(owner) => return Err(rebuild(owner)),
+        };
+
+        // a weak chunk may be upgraded after the check
+        owner.try_unwrap().map_err(|owner| {
+            rebuild(owner.coerce(|arc| arc as Arc<UnsafeCell<dyn AnyChunkOwner<T>>>))
+        })
+    }
+}
+
+impl<S, T> ChunkMut<'static, S, T>
+where
+    S: ChunkOwnerMut<T> + Send + Sync + 'static,
+    T: Send + Sync,
+{
+    /// Erases the owner type of the chunk.
+    pub fn into_dyn(self) -> DynChunkMut<T> {
+        let ChunkMutParts {
+            owner,
+            raw,
+            start,
+            end,
+            partition,
+            tracker,
+            checker,
+            ..
+        } = self.into_parts();
+
+        ChunkMut {
+            owner: owner.coerce(|arc| arc as Arc<UnsafeCell<dyn AnyChunkOwnerMut<T>>>),
+            raw,
+            start,
+            end,
+            partition,
+            tracker,
+            checker,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T> DynChunkMut<T>
+where
+    T: Send + Sync,
+{
+    /// Tries to recover the owning data of type `S`.
+    ///
+    /// The method succeeds if the owner is of type `S`, and the referencing chunk iterator
+    /// and all chunks are dropped. Otherwise, it returns the chunk intact. The data is
+    /// returned in [TryUnwrapError::Poisoned] if any mutable chunk was dropped during panics.
+    pub fn try_unwrap_owner<S>(self) -> TryUnwrapResult<S, Self>
+    where
+        S: ChunkOwnerMut<T> + Send + Sync + 'static,
+    {
+        let ChunkMutParts {
+            owner,
+            raw,
+            start,
+            end,
+            partition,
+            tracker,
+            checker,
+            ..
+        } = self.into_parts();
+
+        let rebuild = |owner| Self {
+            owner,
+            raw,
+            start,
+            end,
+            partition,
+            tracker,
+            checker,
+            _phantom: PhantomData,
+        };
+
+        let owner =
+            match try_cast_as::<_, S>(owner, |data: &dyn AnyChunkOwnerMut<T>| data as &dyn Any) {
+                Ok(owner) => owner,
+                Err(owner) => return Err(TryUnwrapError::Shared(rebuild(owner))),
+            };
+
+        owner.try_recover().map_err(|err| {
+            err.map_shared(|owner| {
+                rebuild(owner.coerce(|arc| arc as Arc<UnsafeCell<dyn AnyChunkOwnerMut<T>>>))
+            })
+        })
+    }
+}
+
+/// Casts the type-erased owner to type `S`, if the owner is unique and of type `S`.
+fn try_cast_as<D, S>(
+    owner: Owner<D>,
+    as_any: impl FnOnce(&D) -> &dyn Any,
+) -> Result<Owner<S>, Owner<D>>
+where
+    D: ?Sized,
+    S: Any,
+{
+    // the type is checked only if the owner is unique, since the data may be mutated by
+    // other chunks
+    let is_owner = owner
+        .get_unique()
+        .is_some_and(|data| as_any(data).is::<S>());
+    if !is_owner {
+        return Err(owner);
+    }
+
+    Ok(unsafe { owner.cast::<S>() })
+}
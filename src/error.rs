@@ -0,0 +1,177 @@
+use crate::{common::*, poison::Poisoned};
+use core::ops::Range;
+
+/// The error returned from fallible chunk operations.
+///
+/// The consumed chunk is returned back inside the error, so that it is not lost
+/// when the operation fails.
+#[derive(Debug)]
+pub enum ChunkError<C> {
+    /// The index exceeds the length of the chunk.
+    OutOfBounds { index: usize, len: usize, chunk: C },
+    /// The indices are not sorted in ascending order.
+    UnsortedIndices { prev: usize, next: usize, chunk: C },
+    /// The sum of lengths does not match the length of the chunk.
+    LengthMismatch { total: usize, len: usize, chunk: C },
+    /// The chunk size is zero while the chunk is not empty.
+    ZeroChunkSize { chunk: C },
+    /// The number of chunks is zero.
+    ZeroNumChunks { chunk: C },
+    /// No chunks are given to concatenate.
+    EmptyChunks,
+    /// The chunk at `index` belongs to an owner different from the first chunk.
+    InconsistentOwner { index: usize, chunks: C },
+    /// The chunk at `index` does not start at the end of its preceding chunk.
+    NotContiguous { index: usize, chunks: C },
+    /// The chunk at `index`, in the order sorted by offsets, overlaps with its preceding chunk.
+    Overlapping { index: usize, chunks: C },
+    /// The ranges of the owner not covered between the chunks.
+    MissingRanges {
+        ranges: Vec<Range<usize>>,
+        chunks: C,
+    },
+    /// The chunk belongs to an owner different from the existing chunks.
+    ForeignOwner { chunk: C },
+}
+
+impl<C> ChunkError<C> {
+    /// Takes back the chunks consumed by the failed operation.
+    ///
+    /// It returns `None` if no chunk was consumed.
+    pub fn into_chunk(self) -> Option<C> {
+        match self {
+            Self::OutOfBounds { chunk, .. }
+            | Self::UnsortedIndices { chunk, .. }
+            | Self::LengthMismatch { chunk, .. }
+            | Self::ZeroChunkSize { chunk }
+            | Self::ZeroNumChunks { chunk }
+            | Self::ForeignOwner { chunk } => Some(chunk),
+            Self::InconsistentOwner { chunks, .. }
+            | Self::NotContiguous { chunks, .. }
+            | Self::Overlapping { chunks, .. }
+            | Self::MissingRanges { chunks, .. } => Some(chunks),
+            Self::EmptyChunks => None,
+        }
+    }
+}
+
+impl<C> fmt::Display for ChunkError<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfBounds { index, len, .. } => {
+                write!(f, "index {} is out of bounds of length {}", index, len)
+            }
+            Self::UnsortedIndices { prev, next, .. } => {
+                write!(
+                    f,
+                    "indices are not sorted: {} is followed by {}",
+                    prev, next
+                )
+            }
+            Self::LengthMismatch { total, len, .. } => {
+                write!(
+                    f,
+                    "the sum of lengths {} does not match the length {}",
+                    total, len
+                )
+            }
+            Self::ZeroChunkSize { .. } => {
+                write!(f, "chunk_size must be positive for non-empty slice")
+            }
+            Self::ZeroNumChunks { .. } => write!(f, "num_chunks must be positive, but get zero"),
+            Self::EmptyChunks => write!(f, "the chunks must be non-empty"),
+            Self::InconsistentOwner { index, .. } => {
+                write!(f, "inconsistent owner of the chunks at index {}", index)
+            }
+            Self::NotContiguous { index, .. } => {
+                write!(f, "the chunks are not contiguous at index {}", index)
+            }
+            Self::Overlapping { index, .. } => {
+                write!(f, "the chunks are overlapping at index {}", index)
+            }
+            Self::MissingRanges { ranges, .. } => {
+                write!(
+                    f,
+                    "the chunks are not contiguous, missing ranges {:?}",
+                    ranges
+                )
+            }
+            Self::ForeignOwner { .. } => write!(f, "the chunk belongs to a foreign owner"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<C> std::error::Error for ChunkError<C> where C: Debug {}
+
+/// The result of recovering the owner of mutable chunks.
+pub type TryUnwrapResult<D, C> = Result<D, TryUnwrapError<D, C>>;
+
+/// The error returned from recovering the owner of mutable chunks.
+///
+/// It is analogous to [TryLockError](std::sync::TryLockError), where the owner is either
+/// still shared or poisoned.
+pub enum TryUnwrapError<D, C> {
+    /// The owner cannot be recovered, mostly because other chunks or chunk iterators still
+    /// refer to it. The consumed value is returned back.
+    Shared(C),
+    /// The owner is recovered, but some of its mutable chunks were dropped during panics.
+    Poisoned(Poisoned<D>),
+}
+
+impl<D, C> TryUnwrapError<D, C> {
+    /// Takes back the consumed value if the owner is still shared.
+    pub fn into_shared(self) -> Option<C> {
+        match self {
+            Self::Shared(value) => Some(value),
+            Self::Poisoned(_) => None,
+        }
+    }
+
+    /// Takes the recovered data if the owner is poisoned.
+    pub fn into_poisoned(self) -> Option<Poisoned<D>> {
+        match self {
+            Self::Shared(_) => None,
+            Self::Poisoned(poisoned) => Some(poisoned),
+        }
+    }
+
+    pub(crate) fn map_shared<E>(self, f: impl FnOnce(C) -> E) -> TryUnwrapError<D, E> {
+        match self {
+            Self::Shared(value) => TryUnwrapError::Shared(f(value)),
+            Self::Poisoned(poisoned) => TryUnwrapError::Poisoned(poisoned),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    pub(crate) fn map_poisoned<E>(self, f: impl FnOnce(D) -> E) -> TryUnwrapError<E, C> {
+        match self {
+            Self::Shared(value) => TryUnwrapError::Shared(value),
+            Self::Poisoned(poisoned) => TryUnwrapError::Poisoned(poisoned.map(f)),
+        }
+    }
+}
+
+impl<D, C> Debug for TryUnwrapError<D, C>
+where
+    C: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Shared(value) => f.debug_tuple("Shared").field(value).finish(),
+            Self::Poisoned(poisoned) => f.debug_tuple("Poisoned").field(poisoned).finish(),
+        }
+    }
+}
+
+impl<D, C> fmt::Display for TryUnwrapError<D, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Shared(_) => write!(f, "the owner is still referenced by other chunks"),
+            Self::Poisoned(poisoned) => write!(f, "{}", poisoned),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<D, C> std::error::Error for TryUnwrapError<D, C> where C: Debug {}
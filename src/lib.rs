@@ -1,12 +1,74 @@
 //! The crate extends slice-type types with methods for concurrent processing.
+//!
+//! The `std` feature is enabled by default. Without it, the crate builds in `no_std`
+//! environments with `alloc`, where the partitioners run on the calling thread and the
+//! [ArcRef](owning_ref::ArcRef) conversions are not available. With `std`, mutable chunks
+//! dropped during panics poison their owner, which is reported when the owner is recovered
+//! or released.
+//!
+//! The jobs over an owner can be cancelled from any of its chunks, and chunk iterators made
+//! `cancellable()` stop yielding chunks once cancelled.
+//!
+//! The `tracking` feature keeps a registry of live chunks per owner, so that the chunks
+//! preventing the owner from being recovered can be listed by the `live_chunks()` methods.
+//!
+//! The `checked` feature asserts at every creation of a mutable chunk that its range does not
+//! overlap with any live mutable chunk on the same owner, turning aliasing bugs into panics.
+//! It is meant for debug builds since every creation and drop takes a global lock.
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+mod array_chunk_mut;
+mod cancel;
+mod checked;
 mod chunk;
 mod chunk_mut;
+mod chunk_owner;
+#[cfg(feature = "std")]
+mod chunk_pool;
+mod chunk_set;
 mod chunks;
 mod chunks_mut;
 mod common;
+mod dyn_chunk;
+mod error;
+mod local_chunk;
+mod local_chunk_mut;
+mod local_chunks;
+mod local_chunks_mut;
+mod on_release;
+mod owner;
+mod partition;
+mod poison;
+mod raw;
+mod tracking;
+mod utils;
+mod weak_chunk;
 
+pub use array_chunk_mut::*;
+pub use cancel::Cancellable;
+#[cfg(feature = "checked")]
+#[doc(hidden)]
+pub use checked::Checker;
 pub use chunk::*;
 pub use chunk_mut::*;
+pub use chunk_owner::*;
+#[cfg(feature = "std")]
+pub use chunk_pool::*;
+pub use chunk_set::*;
 pub use chunks::*;
 pub use chunks_mut::*;
+pub use dyn_chunk::*;
+pub use error::*;
+pub use local_chunk::*;
+pub use local_chunk_mut::*;
+pub use local_chunks::*;
+pub use local_chunks_mut::*;
+pub use on_release::*;
+pub use partition::*;
+pub use poison::Poisoned;
+#[cfg(feature = "tracking")]
+pub use tracking::ChunkInfo;
+pub use weak_chunk::*;
@@ -0,0 +1,402 @@
+use crate::{
+    common::*,
+    error::ChunkError,
+    local_chunks_mut::{LocalEvenChunksMut, LocalIterMut, LocalSizedChunksMut},
+    owner::LocalOwner,
+    raw::RawSlice,
+};
+use core::ops::Range;
+
+/// A mutable sub-slice reference-counted reference to a slice-like data, confined to a single
+/// thread.
+///
+/// It is the single-threaded counterpart of [ChunkMut](crate::ChunkMut) built on [Rc]. It avoids
+/// atomic reference counting, and neither the owner nor the elements are required to be
+/// [Send] or [Sync].
+#[derive(Debug)]
+pub struct LocalChunkMut<'a, S, T>
+where
+    S: ChunkOwnerMut<T> + ?Sized + 'a,
+{
+    pub(super) owner: LocalOwner<S>,
+    pub(super) raw: RawSlice<T>,
+    pub(super) start: usize,
+    pub(super) end: usize,
+    pub(super) _phantom: PhantomData<&'a S>,
+}
+
+impl<'a, S, T> LocalChunkMut<'a, S, T>
+where
+    S: ChunkOwnerMut<T> + ?Sized + 'a,
+{
+    pub fn new(owner: S) -> Self
+    where
+        S: Sized,
+    {
+        Self::from_rc(Rc::new(owner))
+    }
+
+    /// Creates a chunk covering the whole owner.
+    ///
+    /// # Panics
+    /// The method panics if the owner is shared by other [Rc] or [Weak](std::rc::Weak)
+    /// pointers, which would otherwise observe the data while the chunks mutate it.
+    pub fn from_rc(owner: Rc<S>) -> Self {
+        match Self::try_from_rc(owner) {
+            Ok(chunk) => chunk,
+            Err(_) => panic!("the owner must not be shared"),
+        }
+    }
+
+    /// Creates a chunk covering the whole owner.
+    ///
+    /// It is the fallible version of [from_rc()](LocalChunkMut::from_rc), which returns the
+    /// owner back if it is shared.
+    pub fn try_from_rc(mut owner: Rc<S>) -> Result<Self, Rc<S>> {
+        let raw = match Rc::get_mut(&mut owner) {
+            Some(owner) => RawSlice::from_mut(owner.as_mut_slice()),
+            None => return Err(owner),
+        };
+        Ok(unsafe { Self::from_owner_range(LocalOwner::new(owner), raw, 0, raw.len()) })
+    }
+
+    /// Splits the chunk into two sub-chunks, divided at specified index.
+    ///
+    /// # Panics
+    /// The method panics if the index is out of bound.
+    pub fn split_at(self, index: usize) -> (Self, Self) {
+        match self.try_split_at(index) {
+            Ok(pair) => pair,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Splits the chunk into two sub-chunks, divided at specified index.
+    ///
+    /// It returns the chunk back in the error if the index is out of bound.
+    pub fn try_split_at(self, index: usize) -> Result<(Self, Self), ChunkError<Self>> {
+        let len = self.len();
+        if index > len {
+            return Err(ChunkError::OutOfBounds {
+                index,
+                len,
+                chunk: self,
+            });
+        }
+
+        let Self {
+            owner,
+            raw,
+            start,
+            end,
+            ..
+        } = self;
+        let mid = start + index;
+
+        unsafe {
+            Ok((
+                Self::from_owner_range(owner.clone(), raw, start, mid),
+                Self::from_owner_range(owner, raw, mid, end),
+            ))
+        }
+    }
+
+    /// Returns an iterator of roughly fixed-sized chunks of the referencing slice.
+    ///
+    /// # Panics
+    /// The method panics if `chunk_size` is zero and slice length is not zero.
+    pub fn into_sized_chunks(self, chunk_size: usize) -> LocalSizedChunksMut<'a, S, T> {
+        match self.try_into_sized_chunks(chunk_size) {
+            Ok(chunks) => chunks,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Returns an iterator of roughly fixed-sized chunks of the referencing slice.
+    ///
+    /// It is the fallible version of [into_sized_chunks()](LocalChunkMut::into_sized_chunks),
+    /// which returns the chunk back in the error if `chunk_size` is zero and slice length
+    /// is not zero.
+    pub fn try_into_sized_chunks(
+        self,
+        chunk_size: usize,
+    ) -> Result<LocalSizedChunksMut<'a, S, T>, ChunkError<Self>> {
+        if !self.is_empty() && chunk_size == 0 {
+            return Err(ChunkError::ZeroChunkSize { chunk: self });
+        }
+
+        let Self {
+            owner,
+            raw,
+            start,
+            end,
+            ..
+        } = self;
+
+        Ok(LocalSizedChunksMut {
+            chunk_size,
+            index: start,
+            end,
+            owner,
+            raw,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Returns an iterator of evenly sized chunks of the referencing slice.
+    ///
+    /// # Panics
+    /// The method panics if `num_chunks` is zero.
+    pub fn into_even_chunks(self, num_chunks: usize) -> LocalEvenChunksMut<'a, S, T> {
+        match self.try_into_even_chunks(num_chunks) {
+            Ok(chunks) => chunks,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Returns an iterator of evenly sized chunks of the referencing slice.
+    ///
+    /// It is the fallible version of [into_even_chunks()](LocalChunkMut::into_even_chunks),
+    /// which returns the chunk back in the error if `num_chunks` is zero.
+    pub fn try_into_even_chunks(
+        self,
+        num_chunks: usize,
+    ) -> Result<LocalEvenChunksMut<'a, S, T>, ChunkError<Self>> {
+        if num_chunks == 0 {
+            return Err(ChunkError::ZeroNumChunks { chunk: self });
+        }
+
+        let start = self.start;
+        let slice_len = self.len();
+        let Self { owner, raw, .. } = self;
+
+        let base_chunk_size = slice_len / num_chunks;
+        let long_end = start + (slice_len % num_chunks) * (base_chunk_size + 1);
+
+        Ok(LocalEvenChunksMut {
+            index: start,
+            base_chunk_size,
+            long_end,
+            short_end: start + slice_len,
+            owner,
+            raw,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Concatenates contiguous chunks into one chunk.
+    ///
+    /// # Panics
+    /// The method panics if the chunks are not contiguous, or
+    /// the chunks belong to different owners.
+    pub fn cat<I>(chunks: I) -> Self
+    where
+        I: IntoIterator<Item = Self>,
+    {
+        match Self::try_cat(chunks) {
+            Ok(chunk) => chunk,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Concatenates contiguous chunks into one chunk.
+    ///
+    /// It is the fallible version of [cat()](LocalChunkMut::cat), which returns the chunks back
+    /// in the error if the chunks are empty, not contiguous, or belong to different owners.
+    pub fn try_cat<I>(chunks: I) -> Result<Self, ChunkError<Vec<Self>>>
+    where
+        I: IntoIterator<Item = Self>,
+    {
+        let chunks: Vec<_> = chunks.into_iter().collect();
+
+        // obtain inner pointer from the first chunk
+        let (owner, raw) = match chunks.first() {
+            Some(first) => (first.owner.clone(), first.raw),
+            None => return Err(ChunkError::EmptyChunks),
+        };
+
+        // verify if all chunks points to the same owner
+        let inconsistent = chunks.iter().position(|chunk| !chunk.owner.ptr_eq(&owner));
+        if let Some(index) = inconsistent {
+            return Err(ChunkError::InconsistentOwner { index, chunks });
+        }
+
+        // verify if chunks are contiguous
+        let discontiguous = chunks
+            .iter()
+            .zip(chunks.iter().skip(1))
+            .position(|(prev, next)| prev.end != next.start);
+        if let Some(index) = discontiguous {
+            return Err(ChunkError::NotContiguous {
+                index: index + 1,
+                chunks,
+            });
+        }
+
+        let start = chunks.first().unwrap().start;
+        let end = chunks.last().unwrap().end;
+        drop(chunks);
+
+        Ok(unsafe { Self::from_owner_range(owner, raw, start, end) })
+    }
+
+    /// Returns an iterator of owned references to each element of the slice.
+    pub fn into_iter_owned(self) -> LocalIterMut<'a, S, T> {
+        let Self {
+            owner,
+            raw,
+            start,
+            end,
+            ..
+        } = self;
+
+        LocalIterMut {
+            owner,
+            raw,
+            index: start,
+            end,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Converts to the reference-counted owner.
+    ///
+    /// The method succeeds if no other chunk or chunk iterator refers to the owner, which
+    /// could otherwise mutate the data behind the returned [Rc]. Otherwise, it returns the
+    /// chunk intact.
+    pub fn into_rc_owner(self) -> Result<Rc<S>, Self> {
+        let Self {
+            owner,
+            raw,
+            start,
+            end,
+            ..
+        } = self;
+
+        owner
+            .try_into_rc()
+            .map_err(|owner| unsafe { Self::from_owner_range(owner, raw, start, end) })
+    }
+
+    /// Gets the offset of the chunk in the owner.
+    pub fn offset(&self) -> usize {
+        self.start
+    }
+
+    /// Gets the range of the chunk in the owner.
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+
+    /// Gets the reference count on the owning data.
+    pub fn ref_count(&self) -> usize {
+        self.owner.ref_count()
+    }
+
+    /// Creates a chunk referencing to the `start..end` range of the owner.
+    ///
+    /// # Safety
+    /// The base pointer must be taken from the owner by [RawSlice::from_mut], and the caller
+    /// must make sure the range does not overlap with any other living chunk.
+    pub(crate) unsafe fn from_owner_range(
+        owner: LocalOwner<S>,
+        raw: RawSlice<T>,
+        start: usize,
+        end: usize,
+    ) -> Self {
+        assert!(start <= end && end <= raw.len());
+
+        LocalChunkMut {
+            owner,
+            raw,
+            start,
+            end,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, S, T> LocalChunkMut<'a, S, T>
+where
+    S: ChunkOwnerMut<T> + 'a,
+{
+    /// Tries to recover the owning data.
+    ///
+    /// The method succeeds if the referencing chunk iterator and all chunks are dropped.
+    /// Otherwise, it returns the chunk intact.
+    pub fn try_unwrap_owner(self) -> Result<S, Self> {
+        let Self {
+            owner,
+            raw,
+            start,
+            end,
+            ..
+        } = self;
+
+        owner
+            .try_unwrap()
+            .map_err(|owner| unsafe { Self::from_owner_range(owner, raw, start, end) })
+    }
+}
+
+impl<'a, S, T> AsRef<[T]> for LocalChunkMut<'a, S, T>
+where
+    S: ChunkOwnerMut<T> + ?Sized + 'a,
+{
+    fn as_ref(&self) -> &[T] {
+        self.deref()
+    }
+}
+
+impl<'a, S, T> AsMut<[T]> for LocalChunkMut<'a, S, T>
+where
+    S: ChunkOwnerMut<T> + ?Sized + 'a,
+{
+    fn as_mut(&mut self) -> &mut [T] {
+        self.deref_mut()
+    }
+}
+
+impl<'a, S, T> Deref for LocalChunkMut<'a, S, T>
+where
+    S: ChunkOwnerMut<T> + ?Sized + 'a,
+{
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { self.raw.slice(self.start, self.end) }
+    }
+}
+
+impl<'a, S, T> DerefMut for LocalChunkMut<'a, S, T>
+where
+    S: ChunkOwnerMut<T> + ?Sized + 'a,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { self.raw.slice_mut(self.start, self.end) }
+    }
+}
+
+impl<'a, S, T> IntoIterator for &'a LocalChunkMut<'_, S, T>
+where
+    S: ChunkOwnerMut<T> + ?Sized + 'a,
+{
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.deref().iter()
+    }
+}
+
+impl<'a, S, T> IntoIterator for &'a mut LocalChunkMut<'_, S, T>
+where
+    S: ChunkOwnerMut<T> + ?Sized + 'a,
+{
+    type Item = &'a mut T;
+    type IntoIter = slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.deref_mut().iter_mut()
+    }
+}
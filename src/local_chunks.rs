@@ -0,0 +1,289 @@
+use crate::{common::*, local_chunk::LocalChunk, owner::LocalOwner, raw::RawSlice};
+
+pub use local_sized_chunks::*;
+mod local_sized_chunks {
+    use super::*;
+
+    /// An iterator that yields [local chunks](LocalChunk).
+    #[derive(Debug)]
+    pub struct LocalSizedChunks<'a, S, T>
+    where
+        S: ChunkOwner<T> + ?Sized + 'a,
+    {
+        pub(crate) index: usize,
+        pub(crate) chunk_size: usize,
+        pub(crate) end: usize,
+        pub(crate) owner: LocalOwner<S>,
+        pub(crate) raw: RawSlice<T>,
+        pub(crate) _phantom: PhantomData<&'a T>,
+    }
+
+    impl<'a, S, T> LocalSizedChunks<'a, S, T>
+    where
+        S: ChunkOwner<T> + ?Sized + 'a,
+    {
+        /// Tries to recover the owning data.
+        ///
+        /// The method succeeds if the referencing chunk iterator and all chunks are dropped.
+        /// Otherwise, it returns the guard intact.
+        pub fn try_unwrap_owner(self) -> Result<S, Self>
+        where
+            S: Sized,
+        {
+            let Self {
+                index,
+                chunk_size,
+                end,
+                owner,
+                raw,
+                ..
+            } = self;
+
+            owner.try_unwrap().map_err(|owner| Self {
+                index,
+                chunk_size,
+                end,
+                owner,
+                raw,
+                _phantom: PhantomData,
+            })
+        }
+
+        /// Gets the reference count on the owning data.
+        pub fn ref_count(&self) -> usize {
+            self.owner.ref_count()
+        }
+    }
+
+    impl<'a, S, T> Iterator for LocalSizedChunks<'a, S, T>
+    where
+        S: ChunkOwner<T> + ?Sized + 'a,
+    {
+        type Item = LocalChunk<'a, S, T>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            if self.index >= self.end {
+                return None;
+            }
+
+            let start = self.index;
+            let end = start + cmp::min(self.chunk_size, self.end - start);
+            self.index = end;
+
+            Some(LocalChunk::from_owner_range(
+                self.owner.clone(),
+                self.raw,
+                start,
+                end,
+            ))
+        }
+    }
+}
+
+pub use local_even_chunks::*;
+mod local_even_chunks {
+    use super::*;
+
+    /// An iterator that yields [local chunks](LocalChunk).
+    #[derive(Debug)]
+    pub struct LocalEvenChunks<'a, S, T>
+    where
+        S: ChunkOwner<T> + ?Sized + 'a,
+    {
+        pub(crate) base_chunk_size: usize,
+        pub(crate) index: usize,
+        pub(crate) long_end: usize,
+        pub(crate) short_end: usize,
+        pub(crate) owner: LocalOwner<S>,
+        pub(crate) raw: RawSlice<T>,
+        pub(crate) _phantom: PhantomData<&'a T>,
+    }
+
+    impl<'a, S, T> LocalEvenChunks<'a, S, T>
+    where
+        S: ChunkOwner<T> + ?Sized + 'a,
+    {
+        /// Tries to recover the owning data.
+        ///
+        /// The method succeeds if the referencing chunk iterator and all chunks are dropped.
+        /// Otherwise, it returns the guard intact.
+        pub fn try_unwrap_owner(self) -> Result<S, Self>
+        where
+            S: Sized,
+        {
+            let Self {
+                index,
+                base_chunk_size,
+                long_end,
+                short_end,
+                owner,
+                raw,
+                ..
+            } = self;
+
+            owner.try_unwrap().map_err(|owner| Self {
+                index,
+                base_chunk_size,
+                long_end,
+                short_end,
+                owner,
+                raw,
+                _phantom: PhantomData,
+            })
+        }
+
+        /// Gets the reference count on the owning data.
+        pub fn ref_count(&self) -> usize {
+            self.owner.ref_count()
+        }
+    }
+
+    impl<'a, S, T> Iterator for LocalEvenChunks<'a, S, T>
+    where
+        S: ChunkOwner<T> + ?Sized + 'a,
+    {
+        type Item = LocalChunk<'a, S, T>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            debug_assert!(self.long_end <= self.short_end);
+
+            let chunk_size = if self.index < self.long_end {
+                self.base_chunk_size + 1
+            } else if self.index < self.short_end {
+                self.base_chunk_size
+            } else {
+                debug_assert!(self.index == self.short_end);
+                return None;
+            };
+
+            let start = self.index;
+            let end = start + chunk_size;
+            self.index = end;
+
+            Some(LocalChunk::from_owner_range(
+                self.owner.clone(),
+                self.raw,
+                start,
+                end,
+            ))
+        }
+    }
+}
+
+pub use local_iter::*;
+mod local_iter {
+    use super::*;
+
+    /// The iterator returned from [into_iter_owned()](LocalChunk::into_iter_owned).
+    #[derive(Debug)]
+    pub struct LocalIter<'a, S, T>
+    where
+        S: ChunkOwner<T> + ?Sized + 'a,
+    {
+        pub(crate) owner: LocalOwner<S>,
+        pub(crate) raw: RawSlice<T>,
+        pub(crate) index: usize,
+        pub(crate) end: usize,
+        pub(crate) _phantom: PhantomData<&'a T>,
+    }
+
+    impl<'a, S, T> LocalIter<'a, S, T>
+    where
+        S: ChunkOwner<T> + ?Sized + 'a,
+    {
+        pub fn try_unwrap_owner(self) -> Result<S, Self>
+        where
+            S: Sized,
+        {
+            let Self {
+                owner,
+                raw,
+                index,
+                end,
+                ..
+            } = self;
+
+            owner.try_unwrap().map_err(|owner| Self {
+                owner,
+                raw,
+                index,
+                end,
+                _phantom: PhantomData,
+            })
+        }
+    }
+
+    impl<'a, S, T> Iterator for LocalIter<'a, S, T>
+    where
+        S: ChunkOwner<T> + ?Sized + 'a,
+    {
+        type Item = LocalOwned<S, T>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            unsafe {
+                if self.index == self.end {
+                    return None;
+                }
+
+                let ptr = self.raw.get(self.index);
+                self.index += 1;
+
+                Some(LocalOwned {
+                    owner: self.owner.clone(),
+                    ptr,
+                })
+            }
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            let len = self.end - self.index;
+            (len, Some(len))
+        }
+    }
+
+    impl<'a, S, T> ExactSizeIterator for LocalIter<'a, S, T> where S: ChunkOwner<T> + ?Sized + 'a {}
+}
+
+pub use local_owned::*;
+mod local_owned {
+    use super::*;
+
+    /// An owned reference to an element yielded by [LocalIter].
+    pub struct LocalOwned<S: ?Sized, T> {
+        pub(crate) owner: LocalOwner<S>,
+        pub(crate) ptr: NonNull<T>,
+    }
+
+    impl<S: ?Sized, T> LocalOwned<S, T> {
+        pub fn try_unwrap_owner(self) -> Result<S, Self>
+        where
+            S: Sized,
+        {
+            let Self { owner, ptr } = self;
+            owner.try_unwrap().map_err(|owner| Self { owner, ptr })
+        }
+    }
+
+    impl<S: ?Sized, T> AsRef<T> for LocalOwned<S, T> {
+        fn as_ref(&self) -> &T {
+            unsafe { self.ptr.as_ref() }
+        }
+    }
+
+    impl<S: ?Sized, T> Deref for LocalOwned<S, T> {
+        type Target = T;
+
+        fn deref(&self) -> &Self::Target {
+            unsafe { self.ptr.as_ref() }
+        }
+    }
+
+    impl<S: ?Sized, T> Debug for LocalOwned<S, T>
+    where
+        T: Debug,
+    {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            (self.deref()).fmt(f)
+        }
+    }
+}
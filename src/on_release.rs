@@ -0,0 +1,109 @@
+use crate::{common::*, owner::OwnerState, poison::Poisoned};
+use alloc::boxed::Box;
+use core::{mem::ManuallyDrop, ptr};
+
+type ReleaseFn<S> = Box<dyn FnOnce(Result<S, Poisoned<S>>) + Send>;
+
+/// An owner that invokes a callback with the inner data when it is dropped.
+///
+/// Chunks created by [with_on_release()](crate::ChunkMut::with_on_release) share the owner
+/// wrapped in this type. Once the last chunk or chunk iterator referring to the owner is
+/// dropped, the callback runs on that thread and takes the data, e.g. to send it down a
+/// pipeline or to recycle the buffer. If any mutable chunk was dropped during a panic, the
+/// data is passed as [Poisoned] along with the ranges of those chunks.
+///
+/// The callback is not invoked if the data is taken back by
+/// [into_inner()](OnRelease::into_inner).
+pub struct OnRelease<S> {
+    data: ManuallyDrop<S>,
+    callback: ManuallyDrop<ReleaseFn<S>>,
+    state: Arc<OwnerState>,
+}
+
+impl<S> OnRelease<S> {
+    /// Wraps the data with a callback invoked when the wrapper is dropped, which reports the
+    /// poisoned ranges in the owner state.
+    pub(crate) fn new<F>(data: S, state: Arc<OwnerState>, callback: F) -> Self
+    where
+        F: FnOnce(Result<S, Poisoned<S>>) + Send + 'static,
+    {
+        Self {
+            data: ManuallyDrop::new(data),
+            callback: ManuallyDrop::new(Box::new(callback)),
+            state,
+        }
+    }
+
+    /// Takes the data out without invoking the callback.
+    pub fn into_inner(self) -> S {
+        let mut this = ManuallyDrop::new(self);
+        unsafe {
+            ManuallyDrop::drop(&mut this.callback);
+            ptr::drop_in_place(&mut this.state);
+            ManuallyDrop::take(&mut this.data)
+        }
+    }
+}
+
+impl<S> Deref for OnRelease<S> {
+    type Target = S;
+
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+
+impl<S> DerefMut for OnRelease<S> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.data
+    }
+}
+
+impl<S> Drop for OnRelease<S> {
+    fn drop(&mut self) {
+        let (data, callback) = unsafe {
+            (
+                ManuallyDrop::take(&mut self.data),
+                ManuallyDrop::take(&mut self.callback),
+            )
+        };
+        let ranges = self.state.take_poisoned();
+        if ranges.is_empty() {
+            callback(Ok(data));
+        } else {
+            callback(Err(Poisoned::new(data, ranges)));
+        }
+    }
+}
+
+impl<S> Debug for OnRelease<S>
+where
+    S: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OnRelease")
+            .field("data", &*self.data)
+            .finish_non_exhaustive()
+    }
+}
+
+// the callback is only accessed by value when the wrapper is dropped
+unsafe impl<S> Sync for OnRelease<S> where S: Sync {}
+
+unsafe impl<S, T> ChunkOwner<T> for OnRelease<S>
+where
+    S: ChunkOwner<T>,
+{
+    fn as_slice(&self) -> &[T] {
+        self.data.as_slice()
+    }
+}
+
+unsafe impl<S, T> ChunkOwnerMut<T> for OnRelease<S>
+where
+    S: ChunkOwnerMut<T>,
+{
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        self.data.as_mut_slice()
+    }
+}
@@ -0,0 +1,352 @@
+use crate::{
+    common::*,
+    error::{TryUnwrapError, TryUnwrapResult},
+    poison::{self, PoisonMarks},
+};
+use alloc::sync::Weak;
+use core::{
+    cell::UnsafeCell,
+    ops::Range,
+    sync::atomic::{self, AtomicBool, Ordering},
+};
+
+/// The reference-counted owner shared by chunks and chunk iterators.
+///
+/// The data is kept in an [UnsafeCell], so that cloning and dropping the reference, which
+/// borrows the shared allocation, does not assert immutability of the data while it is
+/// mutated by chunks on other threads.
+pub(crate) struct Owner<S: ?Sized> {
+    arc: Arc<UnsafeCell<S>>,
+    state: Arc<OwnerState>,
+}
+
+/// The state shared by all references to an [Owner].
+///
+/// It is kept apart from the data, since the [Arc] on the data may also be referred to
+/// outside the owner.
+#[derive(Debug, Default)]
+pub(crate) struct OwnerState {
+    cancelled: AtomicBool,
+    poisoned: PoisonMarks,
+}
+
+impl OwnerState {
+    /// Sets the cancellation flag.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Release);
+    }
+
+    /// Checks the cancellation flag.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Acquire)
+    }
+
+    /// Marks the range poisoned if the current thread is panicking.
+    pub fn poison_on_panic(&self, range: Range<usize>) {
+        self.poisoned.poison_on_panic(range);
+    }
+
+    /// Removes the poisoned ranges, sorted by offsets.
+    pub fn take_poisoned(&self) -> Vec<Range<usize>> {
+        self.poisoned.take()
+    }
+}
+
+impl<S: ?Sized> Owner<S> {
+    pub fn new(arc: Arc<S>) -> Self {
+        Self::with_state(arc, Arc::default())
+    }
+
+    /// Creates an owner sharing the state given, which may also be referred to by the data.
+    pub fn with_state(arc: Arc<S>, state: Arc<OwnerState>) -> Self {
+        // UnsafeCell<S> has the same in-memory representation as S
+        let arc = unsafe { Arc::from_raw(Arc::into_raw(arc) as *const UnsafeCell<S>) };
+        Self { arc, state }
+    }
+
+    /// Gets the reference count on the owner.
+    pub fn ref_count(&self) -> usize {
+        Arc::strong_count(&self.arc)
+    }
+
+    /// Checks if both point to the same owner.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.arc, &other.arc)
+    }
+
+    /// Gets the address of the owner, which identifies the owner while it is alive.
+    pub fn addr(&self) -> usize {
+        Arc::as_ptr(&self.arc).cast::<()>() as usize
+    }
+
+    /// Lists the live chunks referring to the owner.
+    #[cfg(feature = "tracking")]
+    pub fn live_chunks(&self) -> Vec<ChunkInfo> {
+        crate::tracking::live_chunks(self.addr())
+    }
+
+    /// Gets the state shared by all references to the owner.
+    pub fn state(&self) -> &Arc<OwnerState> {
+        &self.state
+    }
+
+    /// Sets the cancellation flag shared by all references to the owner.
+    pub fn cancel(&self) {
+        self.state.cancel();
+    }
+
+    /// Checks the cancellation flag shared by all references to the owner.
+    pub fn is_cancelled(&self) -> bool {
+        self.state.is_cancelled()
+    }
+
+    /// Marks the range poisoned if the current thread is panicking.
+    pub fn poison_on_panic(&self, range: Range<usize>) {
+        self.state.poison_on_panic(range);
+    }
+
+    /// Creates a weak reference to the owner.
+    pub fn downgrade(&self) -> WeakOwner<S> {
+        WeakOwner {
+            weak: Arc::downgrade(&self.arc),
+            state: self.state.clone(),
+        }
+    }
+
+    /// Converts back to the [Arc] on the data.
+    ///
+    /// # Safety
+    /// The data must not be mutated through other references to the owner as long as
+    /// the returned [Arc] is alive.
+    pub unsafe fn into_arc(self) -> Arc<S> {
+        Arc::from_raw(Arc::into_raw(self.arc) as *const S)
+    }
+
+    /// Converts the owner by an unsizing coercion on the inner [Arc], e.g. into a trait
+    /// object.
+    pub fn coerce<U>(self, f: impl FnOnce(Arc<UnsafeCell<S>>) -> Arc<UnsafeCell<U>>) -> Owner<U>
+    where
+        U: ?Sized,
+    {
+        Owner {
+            arc: f(self.arc),
+            state: self.state,
+        }
+    }
+
+    /// Casts the owner to the concrete type of the data.
+    ///
+    /// # Safety
+    /// The data must be of type `U`.
+    pub unsafe fn cast<U>(self) -> Owner<U> {
+        let arc = Arc::from_raw(Arc::into_raw(self.arc) as *const UnsafeCell<U>);
+        Owner {
+            arc,
+            state: self.state,
+        }
+    }
+
+    /// Checks if no one else refers to the owner, and if so, makes the writes through the
+    /// dropped references visible.
+    ///
+    /// Weak references do not count, since they are only created by immutable chunks, which
+    /// do not mutate the data once upgraded.
+    pub fn is_unique(&self) -> bool {
+        if Arc::strong_count(&self.arc) != 1 {
+            return false;
+        }
+
+        // the count is loaded relaxed, so the fence synchronizes with the release
+        // decrements by dropped owners, making their writes to the data visible
+        atomic::fence(Ordering::Acquire);
+        true
+    }
+
+    /// Gets a shared reference to the data if no one else refers to the owner. See
+    /// [is_unique()](Owner::is_unique).
+    pub fn get_unique(&self) -> Option<&S> {
+        self.is_unique().then(|| unsafe { &*self.arc.get() })
+    }
+
+    /// Converts back to the [Arc] on the data if no one else refers to the owner. See
+    /// [is_unique()](Owner::is_unique).
+    pub fn try_into_arc(self) -> Result<Arc<S>, Self> {
+        if self.is_unique() {
+            Ok(unsafe { self.into_arc() })
+        } else {
+            Err(self)
+        }
+    }
+
+    /// Converts back to the [Arc] on the data like [try_into_arc()](Owner::try_into_arc),
+    /// and reports the ranges poisoned by mutable chunks.
+    pub fn try_recover_arc(self) -> TryUnwrapResult<Arc<S>, Self> {
+        let state = self.state.clone();
+        let arc = self.try_into_arc().map_err(TryUnwrapError::Shared)?;
+        poison::check_poisoned(arc, state.take_poisoned())
+    }
+}
+
+impl<S> Owner<S> {
+    /// Unwraps the data if no one else refers to the owner.
+    pub fn try_unwrap(self) -> Result<S, Self> {
+        let Self { arc, state } = self;
+        Arc::try_unwrap(arc)
+            .map(UnsafeCell::into_inner)
+            .map_err(|arc| Self { arc, state })
+    }
+
+    /// Unwraps the data like [try_unwrap()](Owner::try_unwrap), and reports the ranges
+    /// poisoned by mutable chunks.
+    pub fn try_recover(self) -> TryUnwrapResult<S, Self> {
+        let state = self.state.clone();
+        let data = self.try_unwrap().map_err(TryUnwrapError::Shared)?;
+        poison::check_poisoned(data, state.take_poisoned())
+    }
+}
+
+impl<S: ?Sized> Clone for Owner<S> {
+    fn clone(&self) -> Self {
+        Self {
+            arc: self.arc.clone(),
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<S: ?Sized> Debug for Owner<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // the data is not printed since it may be mutated by chunks on other threads
+        f.debug_struct("Owner")
+            .field("ptr", &Arc::as_ptr(&self.arc))
+            .field("ref_count", &self.ref_count())
+            .finish()
+    }
+}
+
+unsafe impl<S: ?Sized> Send for Owner<S> where S: Send + Sync {}
+
+unsafe impl<S: ?Sized> Sync for Owner<S> where S: Send + Sync {}
+
+/// A weak reference to an [Owner], which does not keep the data alive.
+pub(crate) struct WeakOwner<S: ?Sized> {
+    weak: Weak<UnsafeCell<S>>,
+    state: Arc<OwnerState>,
+}
+
+impl<S: ?Sized> WeakOwner<S> {
+    /// Gets the owner back if the data is still alive.
+    pub fn upgrade(&self) -> Option<Owner<S>> {
+        let arc = self.weak.upgrade()?;
+        Some(Owner {
+            arc,
+            state: self.state.clone(),
+        })
+    }
+
+    /// Gets the reference count on the owner.
+    pub fn ref_count(&self) -> usize {
+        self.weak.strong_count()
+    }
+
+    /// Lists the live chunks referring to the owner.
+    #[cfg(feature = "tracking")]
+    pub fn live_chunks(&self) -> Vec<ChunkInfo> {
+        crate::tracking::live_chunks(self.weak.as_ptr().cast::<()>() as usize)
+    }
+}
+
+impl<S: ?Sized> Clone for WeakOwner<S> {
+    fn clone(&self) -> Self {
+        Self {
+            weak: self.weak.clone(),
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<S: ?Sized> Debug for WeakOwner<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WeakOwner")
+            .field("ptr", &self.weak.as_ptr())
+            .field("ref_count", &self.ref_count())
+            .finish()
+    }
+}
+
+unsafe impl<S: ?Sized> Send for WeakOwner<S> where S: Send + Sync {}
+
+unsafe impl<S: ?Sized> Sync for WeakOwner<S> where S: Send + Sync {}
+
+/// The single-threaded counterpart of [Owner] shared by local chunks and local chunk
+/// iterators.
+pub(crate) struct LocalOwner<S: ?Sized> {
+    rc: Rc<UnsafeCell<S>>,
+}
+
+impl<S: ?Sized> LocalOwner<S> {
+    pub fn new(rc: Rc<S>) -> Self {
+        // UnsafeCell<S> has the same in-memory representation as S
+        let rc = unsafe { Rc::from_raw(Rc::into_raw(rc) as *const UnsafeCell<S>) };
+        Self { rc }
+    }
+
+    /// Gets a mutable reference to the data if no one else refers to the owner.
+    pub fn get_mut(&mut self) -> Option<&mut S> {
+        Rc::get_mut(&mut self.rc).map(UnsafeCell::get_mut)
+    }
+
+    /// Gets the reference count on the owner.
+    pub fn ref_count(&self) -> usize {
+        Rc::strong_count(&self.rc)
+    }
+
+    /// Checks if both point to the same owner.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.rc, &other.rc)
+    }
+
+    /// Converts back to the [Rc] on the data.
+    ///
+    /// # Safety
+    /// The data must not be mutated through other references to the owner as long as
+    /// the returned [Rc] is alive.
+    pub unsafe fn into_rc(self) -> Rc<S> {
+        Rc::from_raw(Rc::into_raw(self.rc) as *const S)
+    }
+
+    /// Converts back to the [Rc] on the data if no one else refers to the owner.
+    pub fn try_into_rc(mut self) -> Result<Rc<S>, Self> {
+        match self.get_mut() {
+            Some(_) => Ok(unsafe { self.into_rc() }),
+            None => Err(self),
+        }
+    }
+}
+
+impl<S> LocalOwner<S> {
+    /// Unwraps the data if no one else refers to the owner.
+    pub fn try_unwrap(self) -> Result<S, Self> {
+        Rc::try_unwrap(self.rc)
+            .map(UnsafeCell::into_inner)
+            .map_err(|rc| Self { rc })
+    }
+}
+
+impl<S: ?Sized> Clone for LocalOwner<S> {
+    fn clone(&self) -> Self {
+        Self {
+            rc: self.rc.clone(),
+        }
+    }
+}
+
+impl<S: ?Sized> Debug for LocalOwner<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // the data is not printed since it may be mutated by other chunks
+        f.debug_struct("LocalOwner")
+            .field("ptr", &Rc::as_ptr(&self.rc))
+            .field("ref_count", &self.ref_count())
+            .finish()
+    }
+}
@@ -0,0 +1,85 @@
+use crate::common::*;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+static NEXT_PARTITION_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// The descriptor of a chunk produced by a partitioner, telling which part of the partition
+/// the chunk is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Partition {
+    /// The identifier unique to each partitioning.
+    pub partition_id: usize,
+    /// The index of the first part covered by the chunk.
+    pub index: usize,
+    /// The number of consecutive parts covered by the chunk. It is 1 unless the chunk
+    /// is concatenated from multiple parts.
+    pub span: usize,
+    /// The total number of parts in the partition.
+    pub count: usize,
+}
+
+impl Partition {
+    /// Merges the descriptors of concatenated chunks.
+    ///
+    /// It returns `None` if any chunk has no descriptor, the chunks belong to different
+    /// partitions, or the parts are not consecutive.
+    pub(crate) fn merge<I>(partitions: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = Option<Self>>,
+    {
+        let mut partitions = partitions.into_iter();
+        let first = partitions.next()??;
+
+        let (start, end, span) = partitions.try_fold(
+            (first.index, first.index + first.span, first.span),
+            |(start, end, span), part| {
+                let part = part?;
+                if part.partition_id != first.partition_id {
+                    return None;
+                }
+                Some((
+                    cmp::min(start, part.index),
+                    cmp::max(end, part.index + part.span),
+                    span + part.span,
+                ))
+            },
+        )?;
+
+        (end - start == span).then_some(Self {
+            index: start,
+            span,
+            ..first
+        })
+    }
+}
+
+/// Generates descriptors of consecutive parts of a new partition.
+#[derive(Debug, Clone)]
+pub(crate) struct PartitionCounter {
+    partition_id: usize,
+    index: usize,
+    count: usize,
+}
+
+impl PartitionCounter {
+    pub(crate) fn new(count: usize) -> Self {
+        Self {
+            partition_id: NEXT_PARTITION_ID.fetch_add(1, Ordering::Relaxed),
+            index: 0,
+            count,
+        }
+    }
+
+    pub(crate) fn next_partition(&mut self) -> Partition {
+        debug_assert!(self.index < self.count);
+        let index = self.index;
+        self.index += 1;
+
+        Partition {
+            partition_id: self.partition_id,
+            index,
+            span: 1,
+            count: self.count,
+        }
+    }
+}
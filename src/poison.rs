@@ -0,0 +1,120 @@
+//! The poisoning of owners whose mutable chunks are dropped during panics.
+//!
+//! A mutable chunk dropped while its thread is unwinding marks its range poisoned on the
+//! owner, which is reported when the owner is recovered or released. The marks are kept in
+//! the state shared by the references to the owner. Without the `std` feature, panics
+//! cannot be detected and no owner is ever poisoned.
+
+use crate::{
+    common::*,
+    error::{TryUnwrapError, TryUnwrapResult},
+};
+use core::ops::Range;
+
+/// The data recovered from an owner with poisoned ranges.
+///
+/// Like [PoisonError](std::sync::PoisonError), it indicates that the data may be left in
+/// an inconsistent state, while the data is still accessible.
+pub struct Poisoned<D> {
+    data: D,
+    ranges: Vec<Range<usize>>,
+}
+
+impl<D> Poisoned<D> {
+    pub(crate) fn new(data: D, ranges: Vec<Range<usize>>) -> Self {
+        Self { data, ranges }
+    }
+
+    /// Gets the ranges of chunks dropped during panics, sorted by offsets.
+    pub fn ranges(&self) -> &[Range<usize>] {
+        &self.ranges
+    }
+
+    /// Gets a reference to the recovered data.
+    pub fn get_ref(&self) -> &D {
+        &self.data
+    }
+
+    /// Gets a mutable reference to the recovered data.
+    pub fn get_mut(&mut self) -> &mut D {
+        &mut self.data
+    }
+
+    /// Takes the recovered data regardless of poisoning.
+    pub fn into_inner(self) -> D {
+        self.data
+    }
+
+    #[cfg(feature = "std")]
+    pub(crate) fn map<E>(self, f: impl FnOnce(D) -> E) -> Poisoned<E> {
+        Poisoned {
+            data: f(self.data),
+            ranges: self.ranges,
+        }
+    }
+}
+
+impl<D> Debug for Poisoned<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // the data is not printed as PoisonError does
+        f.debug_struct("Poisoned")
+            .field("ranges", &self.ranges)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<D> fmt::Display for Poisoned<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "chunks at ranges {:?} were dropped during panics",
+            self.ranges
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl<D> std::error::Error for Poisoned<D> {}
+
+/// Passes the data recovered from the owner, or reports it poisoned with the ranges taken
+/// from the owner.
+pub(crate) fn check_poisoned<D, C>(data: D, ranges: Vec<Range<usize>>) -> TryUnwrapResult<D, C> {
+    if ranges.is_empty() {
+        Ok(data)
+    } else {
+        Err(TryUnwrapError::Poisoned(Poisoned::new(data, ranges)))
+    }
+}
+
+/// The ranges of mutable chunks dropped during panics on an owner.
+#[derive(Debug, Default)]
+pub(crate) struct PoisonMarks {
+    #[cfg(feature = "std")]
+    ranges: std::sync::Mutex<Vec<Range<usize>>>,
+}
+
+#[cfg(feature = "std")]
+impl PoisonMarks {
+    /// Marks the range poisoned if the current thread is panicking.
+    pub fn poison_on_panic(&self, range: Range<usize>) {
+        if thread::panicking() && !range.is_empty() {
+            crate::utils::lock(&self.ranges).push(range);
+        }
+    }
+
+    /// Removes the poisoned ranges, sorted by offsets.
+    pub fn take(&self) -> Vec<Range<usize>> {
+        let mut ranges = mem::take(&mut *crate::utils::lock(&self.ranges));
+        ranges.sort_by_key(|range| range.start);
+        ranges
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl PoisonMarks {
+    pub fn poison_on_panic(&self, _range: Range<usize>) {}
+
+    pub fn take(&self) -> Vec<Range<usize>> {
+        vec![]
+    }
+}
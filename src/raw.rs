@@ -0,0 +1,79 @@
+use crate::common::*;
+
+/// The base pointer and the length of the slice of an owner.
+///
+/// The pointer is obtained once when the owner is wrapped into a chunk. All chunks and
+/// iterators sharing the owner derive their sub-slices from it by pointer arithmetic,
+/// instead of re-borrowing the owner, so that a chunk never invalidates the borrows
+/// held by other chunks.
+#[derive(Debug)]
+pub(crate) struct RawSlice<T> {
+    ptr: NonNull<T>,
+    len: usize,
+}
+
+impl<T> RawSlice<T> {
+    /// Takes the base pointer of a shared slice. The derived sub-slices are read-only.
+    pub fn from_ref(slice: &[T]) -> Self {
+        Self {
+            ptr: NonNull::from(slice).cast(),
+            len: slice.len(),
+        }
+    }
+
+    /// Takes the base pointer of a mutable slice.
+    pub fn from_mut(slice: &mut [T]) -> Self {
+        let len = slice.len();
+        Self {
+            ptr: NonNull::from(slice).cast(),
+            len,
+        }
+    }
+
+    /// Gets the length of the owner slice.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Gets the pointer to the `index`-th element.
+    ///
+    /// # Safety
+    /// The index must not exceed the length of the owner slice.
+    pub unsafe fn get(&self, index: usize) -> NonNull<T> {
+        debug_assert!(index <= self.len);
+        NonNull::new_unchecked(self.ptr.as_ptr().add(index))
+    }
+
+    /// Gets the `start..end` sub-slice of the owner.
+    ///
+    /// # Safety
+    /// The range must be within the owner slice, and must not be mutably borrowed by
+    /// anyone else during the lifetime `'b`.
+    pub unsafe fn slice<'b>(&self, start: usize, end: usize) -> &'b [T] {
+        debug_assert!(start <= end && end <= self.len);
+        slice::from_raw_parts(self.get(start).as_ptr(), end - start)
+    }
+
+    /// Gets the `start..end` mutable sub-slice of the owner.
+    ///
+    /// # Safety
+    /// The base pointer must be taken by [from_mut()](RawSlice::from_mut). The range must
+    /// be within the owner slice, and must not be borrowed by anyone else during the
+    /// lifetime `'b`.
+    pub unsafe fn slice_mut<'b>(&self, start: usize, end: usize) -> &'b mut [T] {
+        debug_assert!(start <= end && end <= self.len);
+        slice::from_raw_parts_mut(self.get(start).as_ptr(), end - start)
+    }
+}
+
+impl<T> Clone for RawSlice<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for RawSlice<T> {}
+
+unsafe impl<T> Send for RawSlice<T> where T: Send + Sync {}
+
+unsafe impl<T> Sync for RawSlice<T> where T: Send + Sync {}
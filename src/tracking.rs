@@ -0,0 +1,108 @@
+//! The registry of live chunks per owner, enabled by the `tracking` feature.
+//!
+//! Each chunk holds a [Tracker] that registers its range along with the thread that created
+//! it, and unregisters it when the chunk is dropped. Without the feature, the tracker is
+//! a zero-sized no-op.
+
+use core::ops::Range;
+
+#[cfg(feature = "tracking")]
+pub use imp::*;
+
+#[cfg(feature = "tracking")]
+mod imp {
+    use super::*;
+    use crate::{common::*, utils};
+    use alloc::{boxed::Box, collections::BTreeMap, string::String};
+    use core::sync::atomic::{AtomicU64, Ordering};
+    use std::{
+        backtrace::{Backtrace, BacktraceStatus},
+        sync::Mutex,
+        thread::ThreadId,
+    };
+
+    static REGISTRY: Mutex<BTreeMap<(usize, u64), ChunkInfo>> = Mutex::new(BTreeMap::new());
+    static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+    /// The description of a live chunk, listed by `live_chunks()` methods.
+    #[derive(Debug, Clone)]
+    pub struct ChunkInfo {
+        /// The range of the chunk in the owner.
+        pub range: Range<usize>,
+        /// The ID of the thread that created the chunk.
+        pub thread_id: ThreadId,
+        /// The name of the thread that created the chunk.
+        pub thread_name: Option<String>,
+        /// The backtrace where the chunk was created. It is captured only if enabled by
+        /// `RUST_BACKTRACE` or `RUST_LIB_BACKTRACE` environment variables.
+        pub backtrace: Option<Arc<Backtrace>>,
+    }
+
+    impl fmt::Display for ChunkInfo {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "chunk {:?} created on thread {:?}",
+                self.range, self.thread_id
+            )?;
+            if let Some(name) = &self.thread_name {
+                write!(f, " '{}'", name)?;
+            }
+            if let Some(backtrace) = &self.backtrace {
+                write!(f, "\n{}", backtrace)?;
+            }
+            Ok(())
+        }
+    }
+
+    /// The registration of a chunk in the registry, which is removed when dropped.
+    #[derive(Debug)]
+    pub(crate) struct Tracker {
+        // boxed to keep the chunk size, as the lock dominates the cost anyway
+        key: Box<(usize, u64)>,
+    }
+
+    impl Tracker {
+        pub fn new(owner: usize, range: Range<usize>) -> Self {
+            let thread = std::thread::current();
+            let backtrace = Backtrace::capture();
+            let info = ChunkInfo {
+                range,
+                thread_id: thread.id(),
+                thread_name: thread.name().map(String::from),
+                backtrace: (backtrace.status() == BacktraceStatus::Captured)
+                    .then(|| Arc::new(backtrace)),
+            };
+
+            let key = (owner, NEXT_ID.fetch_add(1, Ordering::Relaxed));
+            utils::lock(&REGISTRY).insert(key, info);
+            Self { key: Box::new(key) }
+        }
+    }
+
+    impl Drop for Tracker {
+        fn drop(&mut self) {
+            utils::lock(&REGISTRY).remove(&*self.key);
+        }
+    }
+
+    /// Lists the live chunks of the owner at the address, ordered by creation.
+    pub(crate) fn live_chunks(owner: usize) -> Vec<ChunkInfo> {
+        utils::lock(&REGISTRY)
+            .range((owner, 0)..=(owner, u64::MAX))
+            .map(|(_, info)| info.clone())
+            .collect()
+    }
+}
+
+/// The registration of a chunk, which does nothing without the `tracking` feature.
+#[cfg(not(feature = "tracking"))]
+#[derive(Debug)]
+pub(crate) struct Tracker;
+
+#[cfg(not(feature = "tracking"))]
+impl Tracker {
+    pub fn new(_owner: usize, _range: Range<usize>) -> Self {
+        Tracker
+    }
+}
@@ -0,0 +1,157 @@
+use crate::common::*;
+use core::ops::{Bound, Range, RangeBounds};
+#[cfg(feature = "std")]
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// The minimum slice length to search group boundaries on multiple threads, below which
+/// spawning threads costs more than scanning the slice.
+#[cfg(feature = "std")]
+const PARALLEL_SEARCH_MIN_LEN: usize = 1 << 16;
+
+/// Computes the boundaries of `num_chunks` roughly evenly sized partitions of `slice`, which are
+/// shifted forward so that no group, a maximal run of elements where `pred` holds for each
+/// consecutive pair, is divided into two partitions.
+///
+/// The boundaries of long slices are searched on at most one thread per CPU if the `std`
+/// feature is enabled. The returned offsets are relative to the slice, starts with zero, ends
+/// with the slice length and are strictly increasing, unless the slice is empty.
+pub(crate) fn group_boundaries<T, P>(slice: &[T], num_chunks: usize, pred: &P) -> Vec<usize>
+where
+    T: Sync,
+    P: Fn(&T, &T) -> bool + Sync,
+{
+    assert!(num_chunks > 0, "num_chunks must be positive, but get zero");
+
+    let len = slice.len();
+    let find_edge = |mut index: usize| {
+        while index > 0 && index < len && pred(&slice[index - 1], &slice[index]) {
+            index += 1;
+        }
+        index
+    };
+
+    let split_index = |nth: usize| len / num_chunks * nth + cmp::min(nth, len % num_chunks);
+    let search_serial = || -> Vec<usize> {
+        (1..num_chunks)
+            .map(|nth| find_edge(split_index(nth)))
+            .collect()
+    };
+
+    // each worker searches a batch of boundaries, since a search usually scans a few elements
+    #[cfg(feature = "std")]
+    let edges: Vec<usize> = if len < PARALLEL_SEARCH_MIN_LEN || num_chunks <= 2 {
+        search_serial()
+    } else {
+        let nths: Vec<usize> = (1..num_chunks).collect();
+        let num_workers = cmp::min(num_cpus::get(), nths.len()).max(1);
+        let batch_size = cmp::max(nths.len().div_ceil(num_workers), 1);
+
+        thread::scope(|scope| {
+            let handles: Vec<_> = nths
+                .chunks(batch_size)
+                .map(|batch| {
+                    scope.spawn(move || {
+                        batch
+                            .iter()
+                            .map(|&nth| find_edge(split_index(nth)))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().unwrap())
+                .collect()
+        })
+    };
+
+    // there are no threads without std
+    #[cfg(not(feature = "std"))]
+    let edges: Vec<usize> = search_serial();
+
+    let mut boundaries: Vec<usize> = iter::once(0).chain(edges).chain(iter::once(len)).collect();
+    boundaries.dedup();
+    boundaries
+}
+
+/// The reason why sorted ranges do not form a contiguous range.
+pub(crate) enum Discontinuity {
+    /// The range at the index overlaps with its preceding range.
+    Overlapping(usize),
+    /// The ranges not covered between the first and the last range.
+    Missing(Vec<Range<usize>>),
+}
+
+/// Sorts the items by the start of their ranges, and verifies that the ranges are contiguous
+/// and do not overlap.
+///
+/// If `bounds` is given, the ranges must also cover the whole `bounds` range.
+pub(crate) fn sort_contiguous<C, F>(
+    items: &mut [C],
+    bounds: Option<Range<usize>>,
+    range_of: F,
+) -> Result<(), Discontinuity>
+where
+    F: Fn(&C) -> Range<usize>,
+{
+    items.sort_by_key(|item| {
+        let range = range_of(item);
+        (range.start, range.end)
+    });
+
+    let ranges: Vec<_> = items.iter().map(range_of).collect();
+    let mut missing = vec![];
+
+    for (index, (prev, next)) in ranges.iter().zip(ranges.iter().skip(1)).enumerate() {
+        if next.start < prev.end {
+            return Err(Discontinuity::Overlapping(index + 1));
+        }
+        if prev.end < next.start {
+            missing.push(prev.end..next.start);
+        }
+    }
+
+    if let (Some(bounds), Some(first), Some(last)) = (bounds, ranges.first(), ranges.last()) {
+        if bounds.start < first.start {
+            missing.insert(0, bounds.start..first.start);
+        }
+        if last.end < bounds.end {
+            missing.push(last.end..bounds.end);
+        }
+    }
+
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(Discontinuity::Missing(missing))
+    }
+}
+
+/// Locks the mutex even if a holder of the lock panicked.
+///
+/// The mutexes in the crate only guard registries and lists updated by single calls, which
+/// stay consistent across panics.
+#[cfg(feature = "std")]
+pub(crate) fn lock<T: ?Sized>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Resolves `range` to the `start..end` range within a slice of length `len`. It returns
+/// `None` if the range is out of bounds or decreasing.
+pub(crate) fn resolve_range<R>(range: R, len: usize) -> Option<(usize, usize)>
+where
+    R: RangeBounds<usize>,
+{
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start.checked_add(1)?,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end.checked_add(1)?,
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+    (start <= end && end <= len).then_some((start, end))
+}
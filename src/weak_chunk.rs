@@ -0,0 +1,91 @@
+use crate::{chunk::Chunk, common::*, owner::WeakOwner, partition::Partition, raw::RawSlice};
+use core::ops::Range;
+
+/// A weak handle on a [Chunk] created by [downgrade()](Chunk::downgrade).
+///
+/// It keeps the range of the chunk, but does not keep the owner alive. The chunk can be
+/// obtained back by [upgrade()](WeakChunk::upgrade) as long as the data is alive, which is
+/// also the case while the data recovered by [try_unwrap_arc()](Chunk::try_unwrap_arc) is
+/// kept.
+#[derive(Debug)]
+pub struct WeakChunk<'a, S, T>
+where
+    S: ChunkOwner<T> + Send + Sync + ?Sized + 'a,
+    T: Send + Sync,
+{
+    pub(super) owner: WeakOwner<S>,
+    pub(super) raw: RawSlice<T>,
+    pub(super) start: usize,
+    pub(super) end: usize,
+    pub(super) partition: Option<Partition>,
+    pub(super) _phantom: PhantomData<&'a S>,
+}
+
+impl<'a, S, T> WeakChunk<'a, S, T>
+where
+    S: ChunkOwner<T> + Send + Sync + ?Sized + 'a,
+    T: Send + Sync,
+{
+    /// Gets the chunk back if the owning data is still alive.
+    pub fn upgrade(&self) -> Option<Chunk<'a, S, T>> {
+        let owner = self.owner.upgrade()?;
+        let mut chunk = Chunk::from_owner_range(owner, self.raw, self.start, self.end);
+        chunk.partition = self.partition;
+        Some(chunk)
+    }
+
+    /// Gets the offset of the chunk in the owner.
+    pub fn offset(&self) -> usize {
+        self.start
+    }
+
+    /// Gets the range of the chunk in the owner.
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+
+    /// Gets the length of the chunk.
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Checks if the chunk is empty.
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Gets the partition descriptor of the chunk.
+    pub fn partition(&self) -> Option<Partition> {
+        self.partition
+    }
+
+    /// Gets the number of chunks and chunk iterators that keep the owner alive.
+    pub fn ref_count(&self) -> usize {
+        self.owner.ref_count()
+    }
+
+    /// Lists the chunks referring to the owning data, which are still alive.
+    ///
+    /// Chunk iterators and owned elements referring to the data are not listed.
+    #[cfg(feature = "tracking")]
+    pub fn live_chunks(&self) -> Vec<ChunkInfo> {
+        self.owner.live_chunks()
+    }
+}
+
+impl<'a, S, T> Clone for WeakChunk<'a, S, T>
+where
+    S: ChunkOwner<T> + Send + Sync + ?Sized + 'a,
+    T: Send + Sync,
+{
+    fn clone(&self) -> Self {
+        Self {
+            owner: self.owner.clone(),
+            raw: self.raw,
+            start: self.start,
+            end: self.end,
+            partition: self.partition,
+            _phantom: PhantomData,
+        }
+    }
+}
@@ -0,0 +1,60 @@
+#![cfg(feature = "checked")]
+
+use concurrent_slice::{Checker, ChunkMut};
+use std::thread;
+
+#[test]
+fn checked_chunks_test() {
+    // the ranges are handed over without overlapping on splitting and concatenation
+    let chunk = ChunkMut::new(vec![0u32; 12]);
+    let (lchunk, rchunk) = chunk.split_at(4);
+    let chunks = rchunk.split_by_lengths(&[2, 0, 6]).unwrap();
+    let rchunk = ChunkMut::cat(chunks);
+    let rchunk = rchunk.into_range(1..5).unwrap();
+    drop(rchunk);
+
+    let (array, rem) = lchunk.into_array_chunks::<3>();
+    let lchunk = ChunkMut::cat(vec![array.into_chunk(), rem]);
+    assert_eq!(lchunk.range(), 0..4);
+
+    // chunks processed on other threads are released when dropped there
+    let chunks: Vec<_> = lchunk.into_sized_chunks(1).collect();
+    let chunks: Vec<_> = chunks
+        .into_iter()
+        .map(|mut chunk| {
+            thread::spawn(move || {
+                chunk[0] += 1;
+                chunk
+            })
+        })
+        .map(|handle| handle.join().unwrap())
+        .collect();
+    let lchunk = ChunkMut::cat(chunks);
+    drop(lchunk);
+
+    // the whole range is available again once all chunks are dropped
+    let chunk = ChunkMut::new(vec![0u32; 12]);
+    let chunks = chunk.into_even_chunks_by(3, |lhs, rhs| lhs == rhs);
+    let chunk = ChunkMut::cat(chunks);
+    assert_eq!(chunk.range(), 0..12);
+}
+
+#[test]
+fn adjacent_ranges_test() {
+    let owner = Box::new(0u8);
+    let owner = &*owner as *const u8 as usize;
+
+    let _lchecker = Checker::new(owner, 0..4);
+    let _rchecker = Checker::new(owner, 4..8);
+    let _empty = Checker::new(owner, 4..4);
+}
+
+#[test]
+#[should_panic(expected = "overlaps with live mutable chunk")]
+fn overlapping_ranges_test() {
+    let owner = Box::new(0u8);
+    let owner = &*owner as *const u8 as usize;
+
+    let _lchecker = Checker::new(owner, 0..4);
+    let _rchecker = Checker::new(owner, 3..8);
+}
@@ -0,0 +1,48 @@
+#![cfg(feature = "std")]
+
+use concurrent_slice::ChunkPool;
+use std::thread;
+
+#[test]
+fn chunk_pool_test() {
+    let pool = ChunkPool::new(2, || vec![0u32; 8]).with_reset(|buf| buf.fill(0));
+    assert!(pool.is_empty());
+
+    // the owner returns to the pool after all chunks are dropped
+    let chunks: Vec<_> = pool.chunk_mut().into_even_chunks(4).collect();
+    let handles: Vec<_> = chunks
+        .into_iter()
+        .map(|mut chunk| thread::spawn(move || chunk.fill(1)))
+        .collect();
+    handles
+        .into_iter()
+        .for_each(|handle| handle.join().unwrap());
+    assert_eq!(pool.len(), 1);
+
+    // the recycled owner is reset
+    let chunk = pool.chunk_mut();
+    assert!(pool.is_empty());
+    assert_eq!(&*chunk, &[0; 8]);
+
+    // the owners exceeding the capacity are dropped
+    let chunks: Vec<_> = (0..3).map(|_| pool.chunk_mut()).collect();
+    drop(chunk);
+    drop(chunks);
+    assert_eq!(pool.len(), pool.capacity());
+
+    // the owner poisoned by a panicking worker is not recycled
+    let len = pool.len();
+    let chunk = pool.chunk_mut();
+    assert!(thread::spawn(move || {
+        let _chunk = chunk;
+        panic!("worker failed");
+    })
+    .join()
+    .is_err());
+    assert_eq!(pool.len(), len - 1);
+
+    // the owner is dropped if the pool is gone
+    let chunk = pool.chunk_mut();
+    drop(pool);
+    drop(chunk);
+}
@@ -0,0 +1,93 @@
+use concurrent_slice::{ChunkError, ChunkMut, ChunkSet};
+use std::sync::Arc;
+
+#[test]
+fn chunk_set_test() {
+    let owner: Vec<_> = (0..10).collect();
+    let mut chunks: Vec<_> = ChunkMut::new(owner).into_sized_chunks(3).collect();
+    let last = chunks.pop().unwrap();
+    let second = chunks.remove(1);
+
+    let mut set = ChunkSet::new();
+    set.extend(chunks);
+    assert_eq!(set.len(), 2);
+    assert_eq!(set.covered_ranges(), vec![0..3, 6..9]);
+    assert_eq!(set.missing_ranges(), vec![3..6, 9..10]);
+    assert!(!set.is_complete());
+    assert_eq!(&**set.get(7).unwrap(), &[6, 7, 8]);
+    assert!(set.get(4).is_none());
+
+    let other = ChunkMut::new((0..10).collect::<Vec<_>>());
+    assert!(matches!(
+        set.insert(other),
+        Err(ChunkError::ForeignOwner { .. })
+    ));
+
+    set.insert(second).unwrap();
+    assert_eq!(set.covered_ranges(), vec![0..9]);
+    assert_eq!(set.missing_ranges(), vec![9..10]);
+
+    // the set does not hold all references to the owner
+    let mut set = set.try_unwrap_owner().unwrap_err().into_shared().unwrap();
+    set.insert(last).unwrap();
+    assert!(set.is_complete());
+
+    set.merge_adjacent();
+    assert_eq!(set.len(), 1);
+    set.get_mut(0).unwrap()[0] = 10;
+
+    let owner = set.try_unwrap_owner().unwrap();
+    assert_eq!(owner, vec![10, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+}
+
+#[test]
+fn chunk_set_repartition_test() {
+    let owner: Vec<_> = (0..12).collect();
+    let mut set: ChunkSet<_, _> = ChunkSet::new();
+    set.extend(ChunkMut::new(owner).into_even_chunks(3));
+
+    let chunks: Vec<_> = set.repartition().unwrap().into_sized_chunks(5).collect();
+    assert_eq!(chunks.len(), 3);
+    assert_eq!(&*chunks[2], &[10, 11]);
+}
+
+#[test]
+fn chunk_set_unsized_owner_test() {
+    let data: Arc<[u32]> = Arc::from(vec![0; 6]);
+    let mut chunks: Vec<_> = ChunkMut::from_arc(data).into_even_chunks(3).collect();
+    chunks.iter_mut().for_each(|chunk| {
+        let offset = chunk.offset() as u32;
+        chunk.fill(offset);
+    });
+
+    let mut set = ChunkSet::new();
+    set.extend(chunks);
+    let data = set.try_unwrap_arc().unwrap();
+    assert_eq!(&*data, &[0, 0, 2, 2, 4, 4]);
+}
+
+#[test]
+fn chunk_set_empty_chunks_test() {
+    let chunks = ChunkMut::new(vec![0u32; 4])
+        .split_by_lengths(&[2, 0, 0, 2])
+        .unwrap();
+    let mut set = ChunkSet::new();
+    set.extend(chunks);
+    assert_eq!(set.len(), 4);
+    assert!(set.is_complete());
+
+    // empty chunks do not hide the chunk containing the index
+    let mut chunks = ChunkMut::new(vec![0u32; 8])
+        .split_at_indices(&[3, 3])
+        .unwrap();
+    let empty = chunks.remove(1);
+    let mut set = ChunkSet::new();
+    set.insert(ChunkMut::cat(chunks)).unwrap();
+    set.insert(empty).unwrap();
+    assert_eq!(set.get(5).unwrap().range(), 0..8);
+    assert_eq!(set.remove(3).unwrap().range(), 0..8);
+    assert_eq!(set.len(), 1);
+
+    let chunks = set.into_chunks();
+    assert!(chunks[0].is_empty());
+}
@@ -1,5 +1,6 @@
-use concurrent_slice::Chunk;
+use concurrent_slice::{Chunk, ChunkError, ChunkOwner, Partition};
 use itertools::izip;
+use std::sync::Arc;
 
 #[test]
 fn lifetime_test() {
@@ -147,3 +148,433 @@ fn chunks_of_chunk_test() {
     let owner = chunk6.try_unwrap_owner().unwrap();
     assert_eq!(owner, (0..9).collect::<Vec<_>>());
 }
+
+#[test]
+fn exact_chunks_test() {
+    let owner: Vec<_> = (0..8).collect();
+    let mut chunks = Chunk::new(owner).into_exact_chunks(3);
+    assert_eq!(&*chunks.next().unwrap(), &[0, 1, 2]);
+    assert_eq!(&*chunks.next().unwrap(), &[3, 4, 5]);
+    assert!(chunks.next().is_none());
+
+    let remainder = chunks.into_remainder();
+    assert_eq!(&*remainder, &[6, 7]);
+
+    let owner = remainder.try_unwrap_owner().unwrap();
+    assert_eq!(owner, (0..8).collect::<Vec<_>>());
+}
+
+#[test]
+fn rchunks_test() {
+    let owner: Vec<_> = (0..8).collect();
+    let chunks: Vec<_> = Chunk::new(owner).into_rchunks(3).collect();
+    assert_eq!(chunks.len(), 3);
+    assert_eq!(&*chunks[0], &[5, 6, 7]);
+    assert_eq!(&*chunks[1], &[2, 3, 4]);
+    assert_eq!(&*chunks[2], &[0, 1]);
+
+    assert_eq!(Chunk::new([1u8; 0]).into_rchunks(2).count(), 0);
+}
+
+#[test]
+fn rchunks_exact_test() {
+    let owner: Vec<_> = (0..8).collect();
+    let mut chunks = Chunk::new(owner).into_rchunks_exact(3);
+    assert_eq!(&*chunks.next().unwrap(), &[5, 6, 7]);
+    assert_eq!(&*chunks.next().unwrap(), &[2, 3, 4]);
+    assert!(chunks.next().is_none());
+    assert_eq!(&*chunks.into_remainder(), &[0, 1]);
+}
+
+#[test]
+fn split_test() {
+    let owner = vec![1, 0, 2, 3, 0, 0, 4];
+    let chunks: Vec<_> = Chunk::new(owner).into_split(|&x| x == 0).collect();
+    assert_eq!(chunks.len(), 4);
+    assert_eq!(&*chunks[0], &[1]);
+    assert_eq!(&*chunks[1], &[2, 3]);
+    assert_eq!(&*chunks[2], &[] as &[i32]);
+    assert_eq!(&*chunks[3], &[4]);
+
+    let owner = vec![1, 0, 2, 3, 0, 4];
+    let chunks: Vec<_> = Chunk::new(owner).into_splitn(2, |&x| x == 0).collect();
+    assert_eq!(chunks.len(), 2);
+    assert_eq!(&*chunks[0], &[1]);
+    assert_eq!(&*chunks[1], &[2, 3, 0, 4]);
+
+    let owner = vec![1, 0, 2, 3, 0];
+    let chunks: Vec<_> = Chunk::new(owner)
+        .into_split_inclusive(|&x| x == 0)
+        .collect();
+    assert_eq!(chunks.len(), 2);
+    assert_eq!(&*chunks[0], &[1, 0]);
+    assert_eq!(&*chunks[1], &[2, 3, 0]);
+
+    let owner = Chunk::cat(chunks).try_unwrap_owner().unwrap();
+    assert_eq!(owner, vec![1, 0, 2, 3, 0]);
+}
+
+#[test]
+fn chunk_by_test() {
+    let owner = vec![1, 1, 2, 3, 3, 3, 4];
+    let chunks: Vec<_> = Chunk::new(owner)
+        .into_chunk_by(|lhs, rhs| lhs == rhs)
+        .collect();
+    assert_eq!(chunks.len(), 4);
+    assert_eq!(&*chunks[0], &[1, 1]);
+    assert_eq!(&*chunks[1], &[2]);
+    assert_eq!(&*chunks[2], &[3, 3, 3]);
+    assert_eq!(&*chunks[3], &[4]);
+
+    assert_eq!(Chunk::new([1u8; 0]).into_chunk_by(|_, _| true).count(), 0);
+}
+
+#[test]
+fn even_chunks_by_test() {
+    let owner: Vec<_> = (0..100).map(|index| index / 10).collect();
+    let chunks = Chunk::new(owner).into_even_chunks_by(4, |lhs, rhs| lhs == rhs);
+    assert_eq!(chunks.len(), 4);
+    assert_eq!(
+        chunks.iter().map(|chunk| chunk.len()).collect::<Vec<_>>(),
+        vec![30, 20, 30, 20]
+    );
+
+    let owner: Vec<_> = (0..10).map(|index| index / 10).collect();
+    let chunks = Chunk::new(owner).into_even_chunks_by(4, |lhs, rhs| lhs == rhs);
+    assert_eq!(chunks.len(), 1);
+
+    let owner = Chunk::cat(chunks).try_unwrap_owner().unwrap();
+    assert_eq!(owner, vec![0; 10]);
+
+    // far more chunks than CPUs
+    let owner: Vec<u32> = (0..200_000).collect();
+    let chunks = Chunk::new(owner).into_even_chunks_by(50_000, |lhs, rhs| lhs / 4 == rhs / 4);
+    assert_eq!(chunks.len(), 50_000);
+    assert!(chunks
+        .iter()
+        .all(|chunk| chunk.len() == 4 && chunk[0] % 4 == 0));
+}
+
+#[test]
+fn split_at_indices_test() {
+    let owner: Vec<_> = (0..10).collect();
+    let chunks = Chunk::new(owner).split_at_indices(&[2, 2, 7]).unwrap();
+    assert_eq!(chunks.len(), 4);
+    assert_eq!(&*chunks[0], &[0, 1]);
+    assert_eq!(&*chunks[1], &[] as &[i32]);
+    assert_eq!(&*chunks[2], &[2, 3, 4, 5, 6]);
+    assert_eq!(&*chunks[3], &[7, 8, 9]);
+
+    let chunk = Chunk::cat(chunks);
+    let chunk = match chunk.split_at_indices(&[3, 1]) {
+        Err(ChunkError::UnsortedIndices {
+            prev: 3,
+            next: 1,
+            chunk,
+        }) => chunk,
+        _ => unreachable!(),
+    };
+    let chunk = match chunk.split_at_indices(&[3, 11]) {
+        Err(ChunkError::OutOfBounds {
+            index: 11,
+            len: 10,
+            chunk,
+        }) => chunk,
+        _ => unreachable!(),
+    };
+
+    let owner = chunk.try_unwrap_owner().unwrap();
+    assert_eq!(owner, (0..10).collect::<Vec<_>>());
+}
+
+#[test]
+fn split_by_lengths_test() {
+    let owner: Vec<_> = (0..10).collect();
+    let chunks = Chunk::new(owner).split_by_lengths(&[3, 0, 7]).unwrap();
+    assert_eq!(chunks.len(), 3);
+    assert_eq!(&*chunks[0], &[0, 1, 2]);
+    assert_eq!(&*chunks[1], &[] as &[i32]);
+    assert_eq!(&*chunks[2], &[3, 4, 5, 6, 7, 8, 9]);
+
+    let chunk = Chunk::cat(chunks);
+    let err = chunk.split_by_lengths(&[3, 3]).unwrap_err();
+    assert!(matches!(
+        err,
+        ChunkError::LengthMismatch {
+            total: 6,
+            len: 10,
+            ..
+        }
+    ));
+    assert_eq!(err.into_chunk().unwrap().len(), 10);
+}
+
+#[test]
+fn fallible_test() {
+    let owner: Vec<_> = (0..10).collect();
+    let chunk = Chunk::new(owner);
+
+    let chunk = match chunk.try_split_at(11) {
+        Err(ChunkError::OutOfBounds {
+            index: 11,
+            len: 10,
+            chunk,
+        }) => chunk,
+        _ => unreachable!(),
+    };
+    let chunk = match chunk.try_into_sized_chunks(0) {
+        Err(ChunkError::ZeroChunkSize { chunk }) => chunk,
+        _ => unreachable!(),
+    };
+    let chunk = match chunk.try_into_exact_chunks(0) {
+        Err(ChunkError::ZeroChunkSize { chunk }) => chunk,
+        _ => unreachable!(),
+    };
+    let chunk = match chunk.try_into_rchunks(0) {
+        Err(ChunkError::ZeroChunkSize { chunk }) => chunk,
+        _ => unreachable!(),
+    };
+    let chunk = match chunk.try_into_rchunks_exact(0) {
+        Err(ChunkError::ZeroChunkSize { chunk }) => chunk,
+        _ => unreachable!(),
+    };
+    let chunk = match chunk.try_into_even_chunks(0) {
+        Err(ChunkError::ZeroNumChunks { chunk }) => chunk,
+        _ => unreachable!(),
+    };
+    assert!(matches!(
+        Chunk::try_cat(Vec::<Chunk<'_, Vec<i32>, i32>>::new()),
+        Err(ChunkError::EmptyChunks)
+    ));
+
+    let (lchunk, rchunk) = chunk.try_split_at(4).unwrap();
+    let chunks = match Chunk::try_cat(vec![rchunk, lchunk]) {
+        Err(ChunkError::NotContiguous { index: 1, chunks }) => chunks,
+        _ => unreachable!(),
+    };
+    let other = Chunk::new((0..10).collect());
+    let chunks = match Chunk::try_cat(chunks.into_iter().chain(Some(other))) {
+        Err(ChunkError::InconsistentOwner {
+            index: 2,
+            mut chunks,
+        }) => {
+            chunks.pop();
+            chunks
+        }
+        _ => unreachable!(),
+    };
+
+    let chunks = chunks.into_iter().rev();
+    let chunk = Chunk::try_cat(chunks).unwrap();
+    let chunks: Vec<_> = chunk.try_into_sized_chunks(3).unwrap().collect();
+    assert_eq!(chunks.len(), 4);
+    assert_eq!(
+        Chunk::try_cat(chunks).unwrap().try_unwrap_owner().unwrap(),
+        (0..10).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn cat_unordered_test() {
+    let owner: Vec<_> = (0..10).collect();
+    let mut chunks: Vec<_> = Chunk::new(owner).into_sized_chunks(3).collect();
+    chunks.reverse();
+
+    let chunk = Chunk::cat_unordered(chunks);
+    assert_eq!(&*chunk, &(0..10).collect::<Vec<_>>()[..]);
+
+    let mut chunks: Vec<_> = chunk.into_sized_chunks(2).collect();
+    let _ = chunks.remove(3);
+    let _ = chunks.remove(1);
+    chunks.swap(0, 2);
+
+    let chunks = match Chunk::try_cat_unordered(chunks) {
+        Err(ChunkError::MissingRanges { ranges, chunks }) => {
+            assert_eq!(ranges, vec![2..4, 6..8]);
+            chunks
+        }
+        _ => unreachable!(),
+    };
+    assert_eq!(&*chunks[0], &[0, 1]);
+    assert_eq!(&*chunks[1], &[4, 5]);
+    assert_eq!(&*chunks[2], &[8, 9]);
+}
+
+#[test]
+fn global_index_test() {
+    let owner: Vec<_> = (10..20).collect();
+    let root = Chunk::new(owner);
+    assert_eq!(root.range(), 0..10);
+
+    let mut chunks: Vec<_> = root.into_sized_chunks(4).collect();
+    assert_eq!(chunks[1].offset(), 4);
+    assert_eq!(chunks[2].range(), 8..10);
+    assert_eq!(chunks[2].owner_len(), 10);
+
+    let chunk = chunks.remove(1);
+    assert!(chunk
+        .enumerate_global()
+        .all(|(index, &elem)| index + 10 == elem));
+
+    let elems: Vec<_> = chunk.into_iter_owned().enumerate_global().collect();
+    assert_eq!(elems.len(), 4);
+    assert!(elems.iter().all(|(index, elem)| index + 10 == **elem));
+    assert_eq!(elems[0].0, 4);
+}
+
+#[test]
+fn partition_test() {
+    let owner: Vec<_> = (0..10).collect();
+    let root = Chunk::new(owner);
+    assert_eq!(root.partition(), None);
+
+    let chunks: Vec<_> = root.into_even_chunks(4).collect();
+    let partition_id = chunks[0].partition().unwrap().partition_id;
+    chunks.iter().enumerate().for_each(|(index, chunk)| {
+        assert_eq!(
+            chunk.partition(),
+            Some(Partition {
+                partition_id,
+                index,
+                span: 1,
+                count: 4
+            })
+        );
+    });
+
+    let mut chunks = chunks.into_iter();
+    let first = chunks.next().unwrap();
+    let merged = Chunk::cat(chunks);
+    assert_eq!(
+        merged.partition(),
+        Some(Partition {
+            partition_id,
+            index: 1,
+            span: 3,
+            count: 4
+        })
+    );
+
+    let chunks: Vec<_> = merged.into_rchunks(3).collect();
+    assert_eq!(chunks.len(), 3);
+    assert!(chunks
+        .iter()
+        .all(|chunk| chunk.partition().unwrap().partition_id != partition_id));
+    assert_eq!(chunks[2].partition().unwrap().index, 2);
+
+    let merged = Chunk::cat_unordered(chunks);
+    assert_eq!(merged.partition().unwrap().span, 3);
+
+    let whole = Chunk::cat(vec![first, merged]);
+    assert_eq!(whole.partition(), None);
+}
+
+#[test]
+fn chunk_owner_test() {
+    #[derive(Debug)]
+    struct Buffer {
+        data: Vec<u32>,
+    }
+
+    unsafe impl ChunkOwner<u32> for Buffer {
+        fn as_slice(&self) -> &[u32] {
+            &self.data
+        }
+    }
+
+    let buffer = Buffer {
+        data: (0..10).collect(),
+    };
+    let chunks: Vec<_> = Chunk::new(buffer).into_sized_chunks(4).collect();
+    assert_eq!(&*chunks[2], &[8, 9]);
+
+    let boxed: Box<[u32]> = (0..4).collect();
+    let (lchunk, rchunk) = Chunk::new(boxed).split_at(1);
+    assert_eq!((&*lchunk, &*rchunk), (&[0][..], &[1, 2, 3][..]));
+
+    let array = [1u32, 2, 3];
+    let chunk = Chunk::new(&array[..]).into_range(1..).unwrap();
+    assert_eq!(&*chunk, &[2, 3]);
+}
+
+#[test]
+fn unsized_owner_test() {
+    let data: Arc<[f32]> = (0..10).map(|val| val as f32).collect();
+    let mut chunks: Vec<_> = Chunk::from_arc(data).into_sized_chunks(4).collect();
+    assert_eq!(&*chunks[1], &[4.0, 5.0, 6.0, 7.0]);
+
+    let last = chunks.pop().unwrap();
+    let last = last.try_unwrap_arc().unwrap_err();
+    drop(chunks);
+    let data = last.try_unwrap_arc().unwrap();
+    assert_eq!(data.len(), 10);
+
+    let text: Arc<str> = Arc::from("hello world");
+    let (lchunk, rchunk) = Chunk::from_arc(text).split_at(5);
+    assert_eq!((&*lchunk, &*rchunk), (&b"hello"[..], &b" world"[..]));
+    drop(rchunk);
+    assert_eq!(&*lchunk.try_unwrap_arc().unwrap(), "hello world");
+}
+
+#[test]
+fn zero_sized_type_test() {
+    let chunks: Vec<_> = Chunk::new(vec![(); 10]).into_sized_chunks(4).collect();
+    assert_eq!(
+        chunks.iter().map(|chunk| chunk.range()).collect::<Vec<_>>(),
+        vec![0..4, 4..8, 8..10]
+    );
+
+    let chunk = Chunk::cat(chunks);
+    assert_eq!((chunk.offset(), chunk.len()), (0, 10));
+
+    let (lchunk, rchunk) = chunk.split_at(3);
+    assert_eq!((rchunk.offset(), rchunk.len()), (3, 7));
+    assert!(Chunk::try_cat(vec![rchunk, lchunk]).is_err());
+
+    let lens: Vec<_> = Chunk::new(vec![(); 7])
+        .into_even_chunks(3)
+        .map(|chunk| chunk.len())
+        .collect();
+    assert_eq!(lens, [3, 2, 2]);
+
+    // the chunks near the end of the longest slice do not overflow
+    let (_, rchunk) = Chunk::new([(); usize::MAX]).split_at(usize::MAX - 3);
+    let lens: Vec<_> = rchunk
+        .into_sized_chunks(2)
+        .map(|chunk| chunk.len())
+        .collect();
+    assert_eq!(lens, [2, 1]);
+    let (_, rchunk) = Chunk::new([(); usize::MAX]).split_at(usize::MAX - 3);
+    assert_eq!(rchunk.into_windows_owned(5).count(), 0);
+    assert_eq!(Chunk::new([(); 5]).into_windows_owned(2).count(), 4);
+    assert_eq!(Chunk::new([(); 5]).into_iter().count(), 5);
+}
+
+#[test]
+fn weak_chunk_test() {
+    let mut chunks = Chunk::new((0..10).collect::<Vec<u32>>()).into_even_chunks(2);
+    let first = chunks.next().unwrap();
+    let weak = first.downgrade();
+    assert_eq!((weak.range(), weak.len()), (0..5, 5));
+    assert_eq!(weak.partition(), first.partition());
+
+    let upgraded = weak.upgrade().unwrap();
+    assert_eq!(&*upgraded, &[0, 1, 2, 3, 4]);
+    assert_eq!(upgraded.partition(), first.partition());
+    drop((upgraded, first));
+
+    // the weak handle does not keep the owner alive
+    assert_eq!(weak.ref_count(), 1);
+    let owner = chunks.try_unwrap_owner().unwrap();
+    assert_eq!(owner, (0..10).collect::<Vec<_>>());
+    assert!(weak.upgrade().is_none());
+
+    // the weak handle does not prevent recovering the Arc, even for unsized owners
+    let data: Arc<[u32]> = Arc::from(vec![1, 2, 3]);
+    let chunk = Chunk::from_arc(data);
+    let weak = chunk.downgrade();
+    let data = chunk.try_unwrap_arc().unwrap();
+    assert_eq!(&*data, &[1, 2, 3]);
+    drop(data);
+    assert!(weak.upgrade().is_none());
+}
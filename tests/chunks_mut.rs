@@ -1,5 +1,6 @@
-use concurrent_slice::ChunkMut;
+use concurrent_slice::{ChunkError, ChunkMut, ChunkOwner, ChunkOwnerMut, Partition};
 use itertools::izip;
+use std::sync::{mpsc, Arc};
 
 #[test]
 fn lifetime_test() {
@@ -136,3 +137,593 @@ fn chunks_of_chunk_test() {
     let owner = chunk6.try_unwrap_owner().unwrap();
     assert_eq!(owner, (0..9).collect::<Vec<_>>());
 }
+
+#[test]
+fn exact_chunks_test() {
+    let owner: Vec<_> = (0..8).collect();
+    let mut chunks = ChunkMut::new(owner).into_exact_chunks(3);
+    assert_eq!(&*chunks.next().unwrap(), &[0, 1, 2]);
+    assert_eq!(&*chunks.next().unwrap(), &[3, 4, 5]);
+    assert!(chunks.next().is_none());
+
+    let remainder = chunks.into_remainder();
+    assert_eq!(&*remainder, &[6, 7]);
+
+    let owner = remainder.try_unwrap_owner().unwrap();
+    assert_eq!(owner, (0..8).collect::<Vec<_>>());
+}
+
+#[test]
+fn rchunks_test() {
+    let owner: Vec<_> = (0..8).collect();
+    let chunks: Vec<_> = ChunkMut::new(owner).into_rchunks(3).collect();
+    assert_eq!(chunks.len(), 3);
+    assert_eq!(&*chunks[0], &[5, 6, 7]);
+    assert_eq!(&*chunks[1], &[2, 3, 4]);
+    assert_eq!(&*chunks[2], &[0, 1]);
+
+    assert_eq!(ChunkMut::new([1u8; 0]).into_rchunks(2).count(), 0);
+}
+
+#[test]
+fn rchunks_exact_test() {
+    let owner: Vec<_> = (0..8).collect();
+    let mut chunks = ChunkMut::new(owner).into_rchunks_exact(3);
+    assert_eq!(&*chunks.next().unwrap(), &[5, 6, 7]);
+    assert_eq!(&*chunks.next().unwrap(), &[2, 3, 4]);
+    assert!(chunks.next().is_none());
+    assert_eq!(&*chunks.into_remainder(), &[0, 1]);
+}
+
+#[test]
+fn array_chunks_test() {
+    let owner: Vec<_> = (0..8).collect();
+    let (mut arrays, remainder) = ChunkMut::new(owner).into_array_chunks::<3>();
+    assert_eq!(&*arrays, &[[0, 1, 2], [3, 4, 5]]);
+    assert_eq!(&*remainder, &[6, 7]);
+
+    arrays.iter_mut().for_each(|array| array.reverse());
+    drop(remainder);
+
+    let mut iter = arrays.into_iter_owned();
+    assert_eq!(iter.len(), 2);
+    let mut first = iter.next().unwrap();
+    let second = iter.next().unwrap();
+    assert!(iter.next().is_none());
+    assert_eq!(*first, [2, 1, 0]);
+    assert_eq!(*second, [5, 4, 3]);
+
+    first[0] = 9;
+    drop((iter, second));
+
+    let owner = first.try_unwrap_owner().unwrap();
+    assert_eq!(owner, vec![9, 1, 0, 5, 4, 3, 6, 7]);
+}
+
+#[test]
+fn split_test() {
+    let owner = vec![1, 0, 2, 3, 0, 0, 4];
+    let chunks: Vec<_> = ChunkMut::new(owner).into_split(|&x| x == 0).collect();
+    assert_eq!(chunks.len(), 4);
+    assert_eq!(&*chunks[0], &[1]);
+    assert_eq!(&*chunks[1], &[2, 3]);
+    assert_eq!(&*chunks[2], &[] as &[i32]);
+    assert_eq!(&*chunks[3], &[4]);
+
+    let owner = vec![1, 0, 2, 3, 0, 4];
+    let chunks: Vec<_> = ChunkMut::new(owner).into_splitn(2, |&x| x == 0).collect();
+    assert_eq!(chunks.len(), 2);
+    assert_eq!(&*chunks[0], &[1]);
+    assert_eq!(&*chunks[1], &[2, 3, 0, 4]);
+
+    let owner = vec![1, 0, 2, 3, 0];
+    let chunks: Vec<_> = ChunkMut::new(owner)
+        .into_split_inclusive(|&x| x == 0)
+        .collect();
+    assert_eq!(chunks.len(), 2);
+    assert_eq!(&*chunks[0], &[1, 0]);
+    assert_eq!(&*chunks[1], &[2, 3, 0]);
+
+    let owner = ChunkMut::cat(chunks).try_unwrap_owner().unwrap();
+    assert_eq!(owner, vec![1, 0, 2, 3, 0]);
+}
+
+#[test]
+fn chunk_by_test() {
+    let owner = vec![1, 1, 2, 3, 3, 3, 4];
+    let chunks: Vec<_> = ChunkMut::new(owner)
+        .into_chunk_by(|lhs, rhs| lhs == rhs)
+        .collect();
+    assert_eq!(chunks.len(), 4);
+    assert_eq!(&*chunks[0], &[1, 1]);
+    assert_eq!(&*chunks[1], &[2]);
+    assert_eq!(&*chunks[2], &[3, 3, 3]);
+    assert_eq!(&*chunks[3], &[4]);
+
+    assert_eq!(
+        ChunkMut::new([1u8; 0]).into_chunk_by(|_, _| true).count(),
+        0
+    );
+}
+
+#[test]
+fn even_chunks_by_test() {
+    let owner: Vec<_> = (0..100).map(|index| index / 10).collect();
+    let chunks = ChunkMut::new(owner).into_even_chunks_by(4, |lhs, rhs| lhs == rhs);
+    assert_eq!(chunks.len(), 4);
+    assert_eq!(
+        chunks.iter().map(|chunk| chunk.len()).collect::<Vec<_>>(),
+        vec![30, 20, 30, 20]
+    );
+
+    let owner: Vec<_> = (0..10).map(|index| index / 10).collect();
+    let chunks = ChunkMut::new(owner).into_even_chunks_by(4, |lhs, rhs| lhs == rhs);
+    assert_eq!(chunks.len(), 1);
+
+    let owner = ChunkMut::cat(chunks).try_unwrap_owner().unwrap();
+    assert_eq!(owner, vec![0; 10]);
+}
+
+#[test]
+fn split_at_indices_test() {
+    let owner: Vec<_> = (0..10).collect();
+    let chunks = ChunkMut::new(owner).split_at_indices(&[2, 2, 7]).unwrap();
+    assert_eq!(chunks.len(), 4);
+    assert_eq!(&*chunks[0], &[0, 1]);
+    assert_eq!(&*chunks[1], &[] as &[i32]);
+    assert_eq!(&*chunks[2], &[2, 3, 4, 5, 6]);
+    assert_eq!(&*chunks[3], &[7, 8, 9]);
+
+    let chunk = ChunkMut::cat(chunks);
+    let chunk = match chunk.split_at_indices(&[3, 1]) {
+        Err(ChunkError::UnsortedIndices {
+            prev: 3,
+            next: 1,
+            chunk,
+        }) => chunk,
+        _ => unreachable!(),
+    };
+    let chunk = match chunk.split_at_indices(&[3, 11]) {
+        Err(ChunkError::OutOfBounds {
+            index: 11,
+            len: 10,
+            chunk,
+        }) => chunk,
+        _ => unreachable!(),
+    };
+
+    let owner = chunk.try_unwrap_owner().unwrap();
+    assert_eq!(owner, (0..10).collect::<Vec<_>>());
+}
+
+#[test]
+fn split_by_lengths_test() {
+    let owner: Vec<_> = (0..10).collect();
+    let chunks = ChunkMut::new(owner).split_by_lengths(&[3, 0, 7]).unwrap();
+    assert_eq!(chunks.len(), 3);
+    assert_eq!(&*chunks[0], &[0, 1, 2]);
+    assert_eq!(&*chunks[1], &[] as &[i32]);
+    assert_eq!(&*chunks[2], &[3, 4, 5, 6, 7, 8, 9]);
+
+    let chunk = ChunkMut::cat(chunks);
+    let err = chunk.split_by_lengths(&[3, 3]).unwrap_err();
+    assert!(matches!(
+        err,
+        ChunkError::LengthMismatch {
+            total: 6,
+            len: 10,
+            ..
+        }
+    ));
+    assert_eq!(err.into_chunk().unwrap().len(), 10);
+}
+
+#[test]
+fn fallible_test() {
+    let owner: Vec<_> = (0..10).collect();
+    let chunk = ChunkMut::new(owner);
+
+    let chunk = match chunk.try_split_at(11) {
+        Err(ChunkError::OutOfBounds {
+            index: 11,
+            len: 10,
+            chunk,
+        }) => chunk,
+        _ => unreachable!(),
+    };
+    let chunk = match chunk.try_into_sized_chunks(0) {
+        Err(ChunkError::ZeroChunkSize { chunk }) => chunk,
+        _ => unreachable!(),
+    };
+    let chunk = match chunk.try_into_exact_chunks(0) {
+        Err(ChunkError::ZeroChunkSize { chunk }) => chunk,
+        _ => unreachable!(),
+    };
+    let chunk = match chunk.try_into_rchunks(0) {
+        Err(ChunkError::ZeroChunkSize { chunk }) => chunk,
+        _ => unreachable!(),
+    };
+    let chunk = match chunk.try_into_rchunks_exact(0) {
+        Err(ChunkError::ZeroChunkSize { chunk }) => chunk,
+        _ => unreachable!(),
+    };
+    let chunk = match chunk.try_into_even_chunks(0) {
+        Err(ChunkError::ZeroNumChunks { chunk }) => chunk,
+        _ => unreachable!(),
+    };
+    assert!(matches!(
+        ChunkMut::try_cat(Vec::<ChunkMut<'_, Vec<i32>, i32>>::new()),
+        Err(ChunkError::EmptyChunks)
+    ));
+
+    let (lchunk, rchunk) = chunk.try_split_at(4).unwrap();
+    let chunks = match ChunkMut::try_cat(vec![rchunk, lchunk]) {
+        Err(ChunkError::NotContiguous { index: 1, chunks }) => chunks,
+        _ => unreachable!(),
+    };
+    let other = ChunkMut::new((0..10).collect());
+    let chunks = match ChunkMut::try_cat(chunks.into_iter().chain(Some(other))) {
+        Err(ChunkError::InconsistentOwner {
+            index: 2,
+            mut chunks,
+        }) => {
+            chunks.pop();
+            chunks
+        }
+        _ => unreachable!(),
+    };
+
+    let chunks = chunks.into_iter().rev();
+    let chunk = ChunkMut::try_cat(chunks).unwrap();
+    let chunks: Vec<_> = chunk.try_into_sized_chunks(3).unwrap().collect();
+    assert_eq!(chunks.len(), 4);
+    assert_eq!(
+        ChunkMut::try_cat(chunks)
+            .unwrap()
+            .try_unwrap_owner()
+            .unwrap(),
+        (0..10).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn cat_unordered_test() {
+    let owner: Vec<_> = (0..10).collect();
+    let mut chunks: Vec<_> = ChunkMut::new(owner).into_sized_chunks(3).collect();
+    chunks.reverse();
+
+    let chunk = ChunkMut::cat_unordered(chunks);
+    assert_eq!(&*chunk, &(0..10).collect::<Vec<_>>()[..]);
+
+    let mut chunks: Vec<_> = chunk.into_sized_chunks(2).collect();
+    let _ = chunks.remove(3);
+    let _ = chunks.remove(1);
+    chunks.swap(0, 2);
+
+    let chunks = match ChunkMut::try_cat_unordered(chunks) {
+        Err(ChunkError::MissingRanges { ranges, chunks }) => {
+            assert_eq!(ranges, vec![2..4, 6..8]);
+            chunks
+        }
+        _ => unreachable!(),
+    };
+    assert_eq!(&*chunks[0], &[0, 1]);
+    assert_eq!(&*chunks[1], &[4, 5]);
+    assert_eq!(&*chunks[2], &[8, 9]);
+}
+
+#[test]
+fn repartition_test() {
+    let owner: Vec<_> = (0..12).collect();
+    let mut chunks: Vec<_> = ChunkMut::new(owner).into_even_chunks(2).collect();
+    chunks.reverse();
+
+    let root = ChunkMut::repartition(chunks).unwrap();
+    assert_eq!(root.len(), 12);
+
+    let mut chunks: Vec<_> = root.into_even_chunks(4).collect();
+    chunks.iter_mut().enumerate().for_each(|(nth, chunk)| {
+        chunk.iter_mut().for_each(|elem| *elem = nth);
+    });
+    let _ = chunks.remove(0);
+    let _ = chunks.pop();
+
+    let chunks = match ChunkMut::repartition(chunks) {
+        Err(ChunkError::MissingRanges { ranges, chunks }) => {
+            assert_eq!(ranges, vec![0..3, 9..12]);
+            chunks
+        }
+        _ => unreachable!(),
+    };
+    assert_eq!(chunks.len(), 2);
+    drop(chunks);
+
+    let owner = ChunkMut::new(vec![0; 8]);
+    let chunks: Vec<_> = owner.into_sized_chunks(3).collect();
+    let root = ChunkMut::repartition(chunks).unwrap();
+    let owner = root.try_unwrap_owner().unwrap();
+    assert_eq!(owner, vec![0; 8]);
+}
+
+#[test]
+fn global_index_test() {
+    let owner: Vec<_> = (10..20).collect();
+    let root = ChunkMut::new(owner);
+    assert_eq!(root.range(), 0..10);
+
+    let mut chunks: Vec<_> = root.into_sized_chunks(4).collect();
+    assert_eq!(chunks[1].offset(), 4);
+    assert_eq!(chunks[2].range(), 8..10);
+    assert_eq!(chunks[2].owner_len(), 10);
+
+    let mut chunk = chunks.remove(1);
+    assert!(chunk
+        .enumerate_global()
+        .all(|(index, &mut elem)| index + 10 == elem));
+
+    let elems: Vec<_> = chunk.into_iter_owned().enumerate_global().collect();
+    assert_eq!(elems.len(), 4);
+    assert!(elems.iter().all(|(index, elem)| index + 10 == **elem));
+    assert_eq!(elems[0].0, 4);
+}
+
+#[test]
+fn partition_test() {
+    let owner: Vec<_> = (0..10).collect();
+    let root = ChunkMut::new(owner);
+    assert_eq!(root.partition(), None);
+
+    let chunks: Vec<_> = root.into_even_chunks(4).collect();
+    let partition_id = chunks[0].partition().unwrap().partition_id;
+    chunks.iter().enumerate().for_each(|(index, chunk)| {
+        assert_eq!(
+            chunk.partition(),
+            Some(Partition {
+                partition_id,
+                index,
+                span: 1,
+                count: 4
+            })
+        );
+    });
+
+    let mut chunks = chunks.into_iter();
+    let first = chunks.next().unwrap();
+    let merged = ChunkMut::cat(chunks);
+    assert_eq!(
+        merged.partition(),
+        Some(Partition {
+            partition_id,
+            index: 1,
+            span: 3,
+            count: 4
+        })
+    );
+
+    let chunks: Vec<_> = merged.into_rchunks(3).collect();
+    assert_eq!(chunks.len(), 3);
+    assert!(chunks
+        .iter()
+        .all(|chunk| chunk.partition().unwrap().partition_id != partition_id));
+    assert_eq!(chunks[2].partition().unwrap().index, 2);
+
+    let merged = ChunkMut::cat_unordered(chunks);
+    assert_eq!(merged.partition().unwrap().span, 3);
+
+    let whole = ChunkMut::cat(vec![first, merged]);
+    assert_eq!(whole.partition(), None);
+}
+
+#[test]
+fn chunk_owner_test() {
+    #[derive(Debug)]
+    struct Buffer {
+        data: Vec<u32>,
+    }
+
+    unsafe impl ChunkOwner<u32> for Buffer {
+        fn as_slice(&self) -> &[u32] {
+            &self.data
+        }
+    }
+
+    unsafe impl ChunkOwnerMut<u32> for Buffer {
+        fn as_mut_slice(&mut self) -> &mut [u32] {
+            &mut self.data
+        }
+    }
+
+    let buffer = Buffer {
+        data: (0..10).collect(),
+    };
+    let mut chunks = ChunkMut::new(buffer).into_even_chunks(2);
+    chunks.by_ref().for_each(|mut chunk| chunk.reverse());
+    let buffer = chunks.try_unwrap_owner().unwrap();
+    assert_eq!(buffer.data, [4, 3, 2, 1, 0, 9, 8, 7, 6, 5]);
+
+    let boxed: Box<[u32]> = (0..4).collect();
+    let (mut lchunk, rchunk) = ChunkMut::new(boxed).split_at(2);
+    lchunk.fill(7);
+    drop(rchunk);
+    assert_eq!(&*lchunk.try_unwrap_owner().unwrap(), &[7, 7, 2, 3]);
+
+    let slice: &mut [u32] = &mut [0, 1, 2];
+    let mut chunk = ChunkMut::new(&mut *slice);
+    chunk[0] = 5;
+    drop(chunk);
+    assert_eq!(slice, &[5, 1, 2]);
+}
+
+#[test]
+fn unsized_owner_test() {
+    let data: Arc<[u32]> = (0..8).collect();
+    let mut chunks = ChunkMut::from_arc(data).into_sized_chunks(3);
+    chunks.by_ref().for_each(|mut chunk| chunk.reverse());
+    let data = chunks.into_arc_owner().unwrap();
+    assert_eq!(&*data, &[2, 1, 0, 5, 4, 3, 7, 6]);
+
+    let (mut lchunk, rchunk) = ChunkMut::from_arc(data).split_at(4);
+    lchunk.fill(0);
+    let lchunk = lchunk.into_arc_owner().unwrap_err().into_shared().unwrap();
+    drop(rchunk);
+    let data = lchunk.into_arc_owner().unwrap();
+    assert_eq!(&*data, &[0, 0, 0, 0, 4, 3, 7, 6]);
+}
+
+#[test]
+fn zero_sized_type_test() {
+    let chunks: Vec<_> = ChunkMut::new(vec![(); 10]).into_sized_chunks(4).collect();
+    assert_eq!(
+        chunks.iter().map(|chunk| chunk.range()).collect::<Vec<_>>(),
+        vec![0..4, 4..8, 8..10]
+    );
+
+    let chunk = ChunkMut::cat(chunks);
+    assert_eq!((chunk.offset(), chunk.len()), (0, 10));
+
+    let (lchunk, rchunk) = chunk.split_at(3);
+    assert_eq!((rchunk.offset(), rchunk.len()), (3, 7));
+    assert!(ChunkMut::try_cat(vec![rchunk, lchunk]).is_err());
+
+    let lens: Vec<_> = ChunkMut::new(vec![(); 7])
+        .into_even_chunks(3)
+        .map(|chunk| chunk.len())
+        .collect();
+    assert_eq!(lens, [3, 2, 2]);
+
+    // the chunks near the end of the longest slice do not overflow
+    let (_, rchunk) = ChunkMut::new([(); usize::MAX]).split_at(usize::MAX - 3);
+    let lens: Vec<_> = rchunk
+        .into_sized_chunks(2)
+        .map(|chunk| chunk.len())
+        .collect();
+    assert_eq!(lens, [2, 1]);
+    assert_eq!(ChunkMut::new([(); 5]).into_iter().count(), 5);
+}
+
+#[test]
+fn on_release_test() {
+    let (tx, rx) = mpsc::channel();
+    let chunks: Vec<_> = ChunkMut::with_on_release(vec![0u32; 6], move |owner| {
+        tx.send(owner).unwrap();
+    })
+    .into_even_chunks(3)
+    .collect();
+
+    // the callback is invoked once the last chunk is dropped
+    let mut chunks = chunks.into_iter();
+    for (nth, mut chunk) in chunks.by_ref().take(2).enumerate() {
+        chunk.fill(nth as u32 + 1);
+    }
+    assert!(rx.try_recv().is_err());
+    chunks.for_each(|mut chunk| chunk.fill(3));
+    assert_eq!(rx.try_recv().unwrap().unwrap(), [1, 1, 2, 2, 3, 3]);
+
+    // the callback is not invoked if the owner is taken back
+    let (tx, rx) = mpsc::channel();
+    let chunk = ChunkMut::with_on_release(vec![0u32; 2], move |owner| {
+        tx.send(owner).unwrap();
+    });
+    let owner = chunk.try_unwrap_owner().unwrap();
+    assert_eq!(owner.into_inner(), [0, 0]);
+    assert!(rx.recv().is_err());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn poison_test() {
+    use concurrent_slice::TryUnwrapError;
+    use std::{
+        panic::{self, AssertUnwindSafe},
+        thread,
+    };
+
+    let mut chunks = ChunkMut::new(vec![0u32; 12]).into_even_chunks(3);
+    let first = chunks.next().unwrap();
+    let second = chunks.next().unwrap();
+
+    // the chunk is dropped while the worker is unwinding
+    let result = thread::spawn(move || {
+        let mut chunk = second;
+        chunk[0] = 1;
+        panic!("worker failed");
+    })
+    .join();
+    assert!(result.is_err());
+    drop(first);
+
+    let poisoned = match chunks.try_unwrap_owner() {
+        Err(TryUnwrapError::Poisoned(poisoned)) => poisoned,
+        _ => panic!("the owner should be poisoned"),
+    };
+    assert_eq!(poisoned.ranges().len(), 1);
+    assert_eq!(poisoned.ranges()[0], 4..8);
+    assert_eq!(poisoned.into_inner()[4], 1);
+
+    // the chunks consumed without panics do not poison the owner
+    let chunk = ChunkMut::new(vec![0u32; 4]);
+    let (lchunk, rchunk) = chunk.split_at(2);
+    let chunk = ChunkMut::cat(vec![lchunk, rchunk]);
+    assert!(chunk.try_unwrap_owner().is_ok());
+
+    // owned elements dropped during panics also poison the owner
+    let mut elems = ChunkMut::new(vec![0u32; 4]).into_iter_owned();
+    let elem = elems.nth(2).unwrap();
+    let result = panic::catch_unwind(AssertUnwindSafe(move || {
+        let _elem = elem;
+        panic!("worker failed");
+    }));
+    assert!(result.is_err());
+    match elems.try_unwrap_owner() {
+        Err(TryUnwrapError::Poisoned(poisoned)) => assert_eq!(poisoned.ranges()[0], 2..3),
+        _ => panic!("the owner should be poisoned"),
+    }
+
+    // the release callback receives the poisoned data
+    let (tx, rx) = mpsc::channel();
+    let chunk = ChunkMut::with_on_release(vec![0u32; 4], move |owner| {
+        tx.send(owner).unwrap();
+    });
+    assert!(thread::spawn(move || {
+        let mut chunk = chunk;
+        chunk[0] = 1;
+        panic!("worker failed");
+    })
+    .join()
+    .is_err());
+    let poisoned = rx.recv().unwrap().unwrap_err();
+    assert_eq!(poisoned.ranges()[0], 0..4);
+    assert_eq!(poisoned.into_inner()[0], 1);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn cancel_test() {
+    use std::thread;
+
+    let mut chunks = ChunkMut::new(vec![0u32; 12])
+        .into_even_chunks(4)
+        .cancellable();
+    let first = chunks.next().unwrap();
+    assert!(!first.is_cancelled());
+
+    // the cancellation is observed by the iterator and the chunks on other threads
+    first.cancel();
+    assert!(chunks.is_cancelled());
+    assert!(chunks.next().is_none());
+    let cancelled = thread::spawn(move || first.is_cancelled()).join().unwrap();
+    assert!(cancelled);
+
+    // chunks created anew from the recovered data are not cancelled
+    let data = chunks.into_inner().try_unwrap_owner().unwrap();
+    let chunk = ChunkMut::new(data);
+    assert!(!chunk.is_cancelled());
+
+    // owners created separately on the same Arc do not share the flag
+    let arc = Arc::new(vec![0u32; 4]);
+    let chunk = concurrent_slice::Chunk::from_arc(arc.clone());
+    chunk.cancel();
+    let other = concurrent_slice::Chunk::from_arc(arc);
+    assert!(chunk.is_cancelled());
+    assert!(!other.is_cancelled());
+}
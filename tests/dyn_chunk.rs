@@ -0,0 +1,74 @@
+use concurrent_slice::{Chunk, ChunkMut, DynChunk, DynChunkMut};
+use std::{collections::VecDeque, sync::Arc};
+
+#[test]
+fn dyn_chunk_test() {
+    let vec: Vec<u32> = (0..6).collect();
+    let boxed: Box<[u32]> = (6..10).collect();
+
+    let mut queue: VecDeque<DynChunk<u32>> = VecDeque::new();
+    queue.extend(Chunk::new(vec).into_sized_chunks(3).map(Chunk::into_dyn));
+    queue.push_back(Chunk::new(boxed).into_dyn());
+    queue.push_back(Chunk::from_arc(Arc::new([10u32, 11])).into_dyn());
+
+    let sums: Vec<u32> = queue.iter().map(|chunk| chunk.iter().sum()).collect();
+    assert_eq!(sums, [3, 12, 30, 21]);
+
+    let first = queue.pop_front().unwrap();
+    let second = queue.pop_front().unwrap();
+    let merged = Chunk::cat(vec![first, second]);
+    assert_eq!(&*merged, &[0, 1, 2, 3, 4, 5]);
+
+    // the owner type does not match
+    let merged = merged.try_unwrap_owner::<Box<[u32]>>().unwrap_err();
+    assert_eq!(
+        merged.try_unwrap_owner::<Vec<u32>>().unwrap(),
+        [0, 1, 2, 3, 4, 5]
+    );
+
+    let boxed = queue.pop_front().unwrap();
+    let (lchunk, rchunk) = boxed.split_at(2);
+    // the owner is shared by the other chunk
+    let lchunk = lchunk.try_unwrap_owner::<Box<[u32]>>().unwrap_err();
+    drop(rchunk);
+    assert_eq!(
+        &*lchunk.try_unwrap_owner::<Box<[u32]>>().unwrap(),
+        &[6, 7, 8, 9]
+    );
+
+    // weak chunks do not block recovering the owner
+    let chunk = Chunk::new(vec![1u32, 2]).into_dyn();
+    let weak = chunk.downgrade();
+    assert_eq!(chunk.try_unwrap_owner::<Vec<u32>>().unwrap(), [1, 2]);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn dyn_chunk_mut_test() {
+    let chunks: Vec<DynChunkMut<u32>> = vec![
+        ChunkMut::new(vec![1u32, 2, 3]).into_dyn(),
+        ChunkMut::new(Box::new([4u32, 5]) as Box<[u32]>).into_dyn(),
+    ];
+
+    let chunks: Vec<_> = chunks
+        .into_iter()
+        .flat_map(|chunk| chunk.into_sized_chunks(2))
+        .map(|mut chunk| {
+            chunk.iter_mut().for_each(|val| *val *= 10);
+            chunk
+        })
+        .collect();
+    assert_eq!(chunks.len(), 3);
+
+    let mut chunks = chunks.into_iter();
+    let vec = ChunkMut::cat(chunks.by_ref().take(2).collect::<Vec<_>>());
+    assert_eq!(vec.try_unwrap_owner::<Vec<u32>>().unwrap(), [10, 20, 30]);
+
+    let boxed = chunks.next().unwrap();
+    let boxed = boxed
+        .try_unwrap_owner::<Vec<u32>>()
+        .unwrap_err()
+        .into_shared()
+        .unwrap();
+    assert_eq!(&*boxed.try_unwrap_owner::<Box<[u32]>>().unwrap(), &[40, 50]);
+}
@@ -0,0 +1,84 @@
+use concurrent_slice::{ChunkError, LocalChunk, LocalChunkMut};
+use std::{cell::Cell, rc::Rc};
+
+#[test]
+fn local_chunk_test() {
+    // Rc is neither Send nor Sync
+    let owner: Vec<Rc<u32>> = (0..10).map(Rc::new).collect();
+    let chunk = LocalChunk::new(owner);
+
+    let (lchunk, rchunk) = chunk.split_at(4);
+    assert_eq!(lchunk.range(), 0..4);
+    assert_eq!(rchunk.range(), 4..10);
+
+    let sizes: Vec<_> = rchunk
+        .into_sized_chunks(4)
+        .map(|chunk| chunk.len())
+        .collect();
+    assert_eq!(sizes, [4, 2]);
+
+    let chunks: Vec<_> = lchunk.into_even_chunks(3).collect();
+    assert_eq!(
+        chunks.iter().map(|chunk| chunk.range()).collect::<Vec<_>>(),
+        vec![0..2, 2..3, 3..4]
+    );
+    let chunk = LocalChunk::cat(chunks);
+    assert_eq!(chunk.range(), 0..4);
+
+    let values: Vec<u32> = chunk.into_iter_owned().map(|elem| **elem).collect();
+    assert_eq!(values, [0, 1, 2, 3]);
+
+    let (lchunk, rchunk) = LocalChunk::new(vec![1, 2, 3]).split_at(1);
+    let chunks = match LocalChunk::try_cat(vec![rchunk, lchunk]) {
+        Err(ChunkError::NotContiguous { index: 1, chunks }) => chunks,
+        _ => unreachable!(),
+    };
+    let chunk = LocalChunk::cat(chunks.into_iter().rev());
+    assert_eq!(chunk.try_unwrap_owner().unwrap(), [1, 2, 3]);
+
+    let slice: Rc<[u32]> = Rc::from(vec![4, 5, 6]);
+    let (lchunk, rchunk) = LocalChunk::from_rc(slice).split_at(2);
+    let lchunk = lchunk.try_unwrap_rc().unwrap_err();
+    drop(rchunk);
+    assert_eq!(&*lchunk.try_unwrap_rc().unwrap(), &[4, 5, 6]);
+
+    // the chunks near the end of the longest slice do not overflow
+    let (_, rchunk) = LocalChunk::new([(); usize::MAX]).split_at(usize::MAX - 3);
+    assert_eq!(rchunk.into_sized_chunks(2).count(), 2);
+    let (_, rchunk) = LocalChunkMut::new([(); usize::MAX]).split_at(usize::MAX - 3);
+    assert_eq!(rchunk.into_sized_chunks(2).count(), 2);
+}
+
+#[test]
+fn local_chunk_mut_test() {
+    // Cell is not Sync
+    let owner: Vec<Cell<u32>> = (0..10).map(Cell::new).collect();
+    let mut chunks = LocalChunkMut::new(owner).into_even_chunks(3);
+    let mut collected: Vec<_> = chunks.by_ref().collect();
+    collected.iter_mut().for_each(|chunk| {
+        let offset = chunk.offset() as u32;
+        chunk.iter_mut().for_each(|cell| *cell.get_mut() += offset);
+    });
+    drop(collected);
+
+    let owner = chunks.try_unwrap_owner().unwrap();
+    let values: Vec<_> = owner.iter().map(Cell::get).collect();
+    assert_eq!(values, [0, 1, 2, 3, 8, 9, 10, 14, 15, 16]);
+
+    let (mut lchunk, rchunk) = LocalChunkMut::new(vec![0; 6]).split_at(2);
+    lchunk.fill(1);
+    let mut iter = rchunk
+        .into_sized_chunks(3)
+        .flat_map(|chunk| chunk.into_iter_owned());
+    iter.by_ref().for_each(|mut elem| *elem = 2);
+    drop(iter);
+    assert_eq!(lchunk.try_unwrap_owner().unwrap(), [1, 1, 2, 2, 2, 2]);
+
+    let owner = Rc::new(vec![0]);
+    let other = owner.clone();
+    let owner = LocalChunkMut::try_from_rc(owner).unwrap_err();
+    drop(other);
+    let chunk = LocalChunkMut::from_rc(owner);
+    let chunk = LocalChunkMut::cat(vec![chunk]);
+    assert_eq!(*chunk.into_rc_owner().unwrap(), [0]);
+}
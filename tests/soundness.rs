@@ -0,0 +1,457 @@
+//! Exercises every constructor and iterator of the thread-safe chunks with chunks processed
+//! on concurrent threads. The local chunks are left out, since they cannot be sent to other
+//! threads. The tests are kept small so that they can run under Miri.
+
+#[cfg(feature = "std")]
+use concurrent_slice::ChunkPool;
+use concurrent_slice::{
+    Chunk, ChunkMut, ChunkOwner, ChunkOwnerMut, ChunkSet, DynChunk, DynChunkMut,
+};
+use std::{
+    sync::{mpsc, Arc},
+    thread,
+};
+
+const LEN: usize = 16;
+const SEP: usize = usize::MAX;
+
+/// Writes the global index to each element, where each chunk is processed on its own thread.
+fn write_concurrently<S, I>(chunks: I) -> Vec<ChunkMut<'static, S, usize>>
+where
+    S: ChunkOwnerMut<usize> + Send + Sync + ?Sized + 'static,
+    I: IntoIterator<Item = ChunkMut<'static, S, usize>>,
+{
+    let handles: Vec<_> = chunks
+        .into_iter()
+        .map(|mut chunk| {
+            thread::spawn(move || {
+                chunk
+                    .enumerate_global()
+                    .for_each(|(index, elem)| *elem = index);
+                chunk
+            })
+        })
+        .collect();
+    handles
+        .into_iter()
+        .map(|handle| handle.join().unwrap())
+        .collect()
+}
+
+/// Verifies that each element equals its global index, where each chunk is processed on its
+/// own thread.
+fn read_concurrently<S, I>(chunks: I) -> Vec<Chunk<'static, S, usize>>
+where
+    S: ChunkOwner<usize> + Send + Sync + ?Sized + 'static,
+    I: IntoIterator<Item = Chunk<'static, S, usize>>,
+{
+    let handles: Vec<_> = chunks
+        .into_iter()
+        .map(|chunk| {
+            thread::spawn(move || {
+                assert!(chunk.enumerate_global().all(|(index, &elem)| index == elem));
+                chunk
+            })
+        })
+        .collect();
+    handles
+        .into_iter()
+        .map(|handle| handle.join().unwrap())
+        .collect()
+}
+
+/// Recovers the owner from the chunks holding all references to it.
+fn recover<S>(chunks: Vec<ChunkMut<'static, S, usize>>) -> S
+where
+    S: ChunkOwnerMut<usize> + Send + Sync + 'static,
+{
+    let mut set = ChunkSet::new();
+    set.extend(chunks);
+    set.try_unwrap_owner().ok().unwrap()
+}
+
+fn indices() -> Vec<usize> {
+    (0..LEN).collect()
+}
+
+fn with_separators() -> Vec<usize> {
+    (0..LEN)
+        .map(|index| if index % 5 == 4 { SEP } else { index })
+        .collect()
+}
+
+#[test]
+fn constructors_test() {
+    let chunk = ChunkMut::new(vec![0; LEN]);
+    let owner = recover(write_concurrently([chunk]));
+    assert_eq!(owner, indices());
+
+    let chunk = ChunkMut::from_arc(Arc::new(vec![0; LEN]));
+    let owner = recover(write_concurrently([chunk]));
+    assert_eq!(owner, indices());
+
+    let shared = Arc::new(vec![0; LEN]);
+    let other = ChunkMut::try_from_arc(shared.clone()).unwrap_err();
+    drop(shared);
+    assert!(ChunkMut::try_from_arc(other).is_ok());
+
+    let chunk = Chunk::new(indices());
+    read_concurrently([chunk.to_range(..).unwrap(), chunk]);
+
+    let shared = Arc::new(indices());
+    let chunks = read_concurrently([Chunk::from_arc(shared.clone()), Chunk::from_arc(shared)]);
+    drop(chunks);
+}
+
+#[test]
+fn split_test() {
+    let (lchunk, rchunk) = ChunkMut::new(vec![0; LEN]).split_at(5);
+    let (mchunk, rchunk) = rchunk.try_split_at(6).ok().unwrap();
+    let owner = recover(write_concurrently([lchunk, mchunk, rchunk]));
+    assert_eq!(owner, indices());
+
+    let chunks = ChunkMut::new(vec![0; LEN])
+        .split_at_indices(&[3, 3, 10])
+        .unwrap();
+    let owner = recover(write_concurrently(chunks));
+    assert_eq!(owner, indices());
+
+    let chunks = ChunkMut::new(vec![0; LEN])
+        .split_by_lengths(&[7, 0, 9])
+        .unwrap();
+    let owner = recover(write_concurrently(chunks));
+    assert_eq!(owner, indices());
+
+    let (lchunk, rchunk) = Chunk::new(indices()).split_at(5);
+    read_concurrently(lchunk.split_at_indices(&[1, 4]).unwrap());
+    read_concurrently(rchunk.split_by_lengths(&[10, 1]).unwrap());
+}
+
+#[test]
+fn partition_test() {
+    let mut chunks = ChunkMut::new(vec![0; LEN]).into_sized_chunks(3);
+    drop(write_concurrently(chunks.by_ref()));
+    assert_eq!(chunks.try_unwrap_owner().unwrap(), indices());
+
+    let mut chunks = ChunkMut::new(vec![0; LEN]).into_even_chunks(5);
+    drop(write_concurrently(chunks.by_ref()));
+    assert_eq!(chunks.try_unwrap_owner().unwrap(), indices());
+
+    let mut chunks = ChunkMut::new(vec![0; LEN]).into_rchunks(3);
+    drop(write_concurrently(chunks.by_ref()));
+    assert_eq!(chunks.try_unwrap_owner().unwrap(), indices());
+
+    let mut chunks = ChunkMut::new(vec![0; LEN]).into_exact_chunks(3);
+    let mut written = write_concurrently(chunks.by_ref());
+    written.push(chunks.into_remainder());
+    let owner = recover(write_concurrently(written));
+    assert_eq!(owner, indices());
+
+    let mut chunks = ChunkMut::new(vec![0; LEN]).into_rchunks_exact(3);
+    let mut written = write_concurrently(chunks.by_ref());
+    written.push(chunks.into_remainder());
+    let owner = recover(write_concurrently(written));
+    assert_eq!(owner, indices());
+
+    read_concurrently(Chunk::new(indices()).into_sized_chunks(3));
+    read_concurrently(Chunk::new(indices()).into_even_chunks(5));
+    read_concurrently(Chunk::new(indices()).into_rchunks(3));
+
+    let mut chunks = Chunk::new(indices()).into_exact_chunks(3);
+    read_concurrently(chunks.by_ref());
+    read_concurrently([chunks.into_remainder()]);
+
+    let mut chunks = Chunk::new(indices()).into_rchunks_exact(3);
+    read_concurrently(chunks.by_ref());
+    read_concurrently([chunks.into_remainder()]);
+}
+
+#[test]
+fn split_by_pred_test() {
+    let is_sep = |&elem: &usize| elem == SEP;
+
+    let mut chunks = ChunkMut::new(with_separators()).into_split(is_sep);
+    drop(write_concurrently(chunks.by_ref()));
+    let owner = chunks.try_unwrap_owner().ok().unwrap();
+    assert_eq!(owner, with_separators());
+
+    let mut chunks = ChunkMut::new(with_separators()).into_splitn(2, is_sep);
+    drop(write_concurrently(chunks.by_ref()));
+    let owner = chunks.try_unwrap_owner().ok().unwrap();
+    assert_eq!(&owner[0..4], &indices()[0..4]);
+    assert_eq!(&owner[5..], &indices()[5..]);
+
+    let chunks = ChunkMut::new(with_separators()).into_split_inclusive(is_sep);
+    let owner = recover(write_concurrently(chunks));
+    assert_eq!(owner, indices());
+
+    let chunks = ChunkMut::new(vec![0; LEN]).into_chunk_by(|_, _| false);
+    let owner = recover(write_concurrently(chunks));
+    assert_eq!(owner, indices());
+
+    let chunks = ChunkMut::new(vec![0; LEN]).into_even_chunks_by(3, |_, _| false);
+    let owner = recover(write_concurrently(chunks));
+    assert_eq!(owner, indices());
+
+    let is_odd = |&elem: &usize| elem % 2 == 1;
+    let chunks: Vec<_> = Chunk::new(indices()).into_split(is_odd).collect();
+    assert_eq!(chunks.len(), LEN / 2 + 1);
+    read_concurrently(chunks);
+    read_concurrently(Chunk::new(indices()).into_splitn(3, is_odd));
+    read_concurrently(Chunk::new(indices()).into_split_inclusive(is_odd));
+    read_concurrently(Chunk::new(indices()).into_chunk_by(|lhs, rhs| lhs / 4 == rhs / 4));
+    read_concurrently(Chunk::new(indices()).into_even_chunks_by(3, |lhs, rhs| lhs / 4 == rhs / 4));
+}
+
+#[test]
+fn element_iter_test() {
+    // the owned elements are not sendable, so the iterators are created on the chunk threads
+    let mut chunks = ChunkMut::new(vec![0; LEN]).into_even_chunks(3);
+    let handles: Vec<_> = chunks
+        .by_ref()
+        .map(|chunk| {
+            thread::spawn(move || {
+                let mut iter = chunk.into_iter_owned().enumerate_global();
+                let elems: Vec<_> = iter
+                    .by_ref()
+                    .map(|(index, mut elem)| {
+                        *elem = index;
+                        elem
+                    })
+                    .collect();
+                drop(elems);
+                drop(iter.into_inner());
+            })
+        })
+        .collect();
+    handles
+        .into_iter()
+        .for_each(|handle| handle.join().unwrap());
+    assert_eq!(chunks.try_unwrap_owner().unwrap(), indices());
+
+    let (arrays, rest) = ChunkMut::new(vec![0; LEN + 1]).into_array_chunks::<3>();
+    let rest = write_concurrently([rest]);
+    let mut iter = arrays.into_iter_owned();
+    let arrays: Vec<_> = iter
+        .by_ref()
+        .map(|mut array| {
+            array.iter_mut().for_each(|elem| *elem = 1);
+            array
+        })
+        .collect();
+    drop((arrays, rest));
+    let owner = iter.try_unwrap_owner().unwrap();
+    assert_eq!(&owner[..(LEN / 3 * 3)], &[1; LEN / 3 * 3]);
+    assert_eq!(&owner[(LEN / 3 * 3)..], &[15, 16]);
+
+    let chunks = Chunk::new(indices()).into_even_chunks(3);
+    let handles: Vec<_> = chunks
+        .map(|chunk| {
+            thread::spawn(move || {
+                let mut iter = chunk.into_iter_owned().enumerate_global();
+                assert!(iter.by_ref().all(|(index, elem)| *elem == index));
+            })
+        })
+        .collect();
+    handles
+        .into_iter()
+        .for_each(|handle| handle.join().unwrap());
+
+    let windows = Chunk::new(indices()).into_windows_owned(4);
+    assert_eq!(read_concurrently(windows).len(), LEN - 3);
+}
+
+#[test]
+fn array_chunk_test() {
+    let (mut arrays, rest) = ChunkMut::new(vec![0; LEN]).into_array_chunks::<4>();
+    let rest = write_concurrently([rest]);
+    let handle = thread::spawn(move || {
+        arrays
+            .iter_mut()
+            .flatten()
+            .enumerate()
+            .for_each(|(index, elem)| *elem = index);
+        arrays.into_chunk()
+    });
+    let mut chunks = rest;
+    chunks.push(handle.join().unwrap());
+    assert_eq!(recover(chunks), indices());
+}
+
+#[test]
+fn concat_test() {
+    let chunks: Vec<_> = ChunkMut::new(vec![0; LEN]).into_sized_chunks(3).collect();
+    let mut chunks = write_concurrently(chunks);
+    let tail = chunks.split_off(2);
+    let head = ChunkMut::cat(chunks);
+    let tail = ChunkMut::cat_unordered(tail.into_iter().rev());
+    let chunk = ChunkMut::repartition([tail, head]).ok().unwrap();
+    let mut chunks = chunk.into_range(2..).unwrap().into_even_chunks(4);
+    drop(write_concurrently(chunks.by_ref()));
+    assert_eq!(chunks.try_unwrap_owner().unwrap(), indices());
+
+    let chunks = read_concurrently(Chunk::new(indices()).into_sized_chunks(3));
+    let chunk = Chunk::cat_unordered(chunks.into_iter().rev());
+    let sub = chunk.to_range(3..=9).unwrap();
+    read_concurrently([sub, chunk.into_range(..).unwrap()]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn arc_ref_test() {
+    let chunks: Vec<_> = ChunkMut::new(vec![0; LEN]).into_even_chunks(2).collect();
+    let mut chunks = write_concurrently(chunks);
+    let rchunk = chunks.pop().unwrap();
+
+    // the data is still mutable through the other chunk
+    let rchunk = rchunk.into_arc_ref().unwrap_err().into_shared().unwrap();
+    let rchunk = rchunk.into_arc_owner().unwrap_err().into_shared().unwrap();
+    drop(chunks);
+    assert_eq!(&*rchunk.into_arc_ref().ok().unwrap(), &indices()[8..]);
+
+    let (lchunk, rchunk) = Chunk::new(indices()).split_at(8);
+    let handle = thread::spawn(move || rchunk.into_arc_ref());
+    assert_eq!(&*lchunk.into_arc_ref(), &indices()[..8]);
+    assert_eq!(&*handle.join().unwrap(), &indices()[8..]);
+}
+
+#[test]
+fn inline_owner_test() {
+    let mut chunks = ChunkMut::new([0; LEN]).into_sized_chunks(5);
+    drop(write_concurrently(chunks.by_ref()));
+    assert_eq!(&chunks.try_unwrap_owner().unwrap()[..], &indices()[..]);
+}
+
+#[test]
+fn weak_chunk_test() {
+    let chunks: Vec<_> = Chunk::new(indices()).into_even_chunks(4).collect();
+    let weaks: Vec<_> = chunks.iter().map(Chunk::downgrade).collect();
+
+    // monitors peek at the chunks while they are processed
+    let monitors: Vec<_> = weaks
+        .into_iter()
+        .map(|weak| {
+            thread::spawn(move || {
+                if let Some(chunk) = weak.upgrade() {
+                    assert!(chunk.enumerate_global().all(|(index, &elem)| index == elem));
+                }
+                weak
+            })
+        })
+        .collect();
+    let chunks = read_concurrently(chunks);
+
+    let weaks: Vec<_> = monitors
+        .into_iter()
+        .map(|handle| handle.join().unwrap())
+        .collect();
+    let owner = Chunk::cat(chunks).try_unwrap_owner().unwrap();
+    assert_eq!(owner, indices());
+    assert!(weaks.iter().all(|weak| weak.upgrade().is_none()));
+
+    // the monitor upgrades only after the owner is recovered on the other thread
+    let chunk = Chunk::new(indices());
+    let weak = chunk.downgrade();
+    let (tx, rx) = mpsc::channel();
+    let monitor = thread::spawn(move || {
+        rx.recv().unwrap();
+        weak.upgrade().is_none()
+    });
+    assert_eq!(chunk.try_unwrap_owner().unwrap(), indices());
+    tx.send(()).unwrap();
+    assert!(monitor.join().unwrap());
+}
+
+#[test]
+fn dyn_chunk_test() {
+    let chunks: Vec<DynChunkMut<usize>> = ChunkMut::new(vec![0; LEN])
+        .into_sized_chunks(5)
+        .map(ChunkMut::into_dyn)
+        .collect();
+    let chunk = ChunkMut::cat(write_concurrently(chunks));
+    let owner = chunk.try_unwrap_owner::<Vec<usize>>().ok().unwrap();
+    assert_eq!(owner, indices());
+
+    let boxed: Box<[usize]> = indices().into();
+    let chunks: Vec<DynChunk<usize>> = Chunk::new(boxed)
+        .into_even_chunks(3)
+        .map(Chunk::into_dyn)
+        .collect();
+    let chunk = Chunk::cat(read_concurrently(chunks));
+    let owner = chunk.try_unwrap_owner::<Box<[usize]>>().unwrap();
+    assert_eq!(&owner[..], &indices()[..]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn chunk_pool_test() {
+    let pool = ChunkPool::new(1, || vec![0; LEN]);
+
+    // the owner is recycled on whichever thread drops the last chunk
+    let chunks = pool.chunk_mut().into_even_chunks(4);
+    let handles: Vec<_> = chunks
+        .map(|mut chunk| {
+            thread::spawn(move || {
+                chunk
+                    .enumerate_global()
+                    .for_each(|(index, elem)| *elem = index);
+            })
+        })
+        .collect();
+    handles
+        .into_iter()
+        .for_each(|handle| handle.join().unwrap());
+
+    let chunk = pool.chunk_mut::<usize>();
+    assert!(pool.is_empty());
+    assert_eq!(&*chunk, &indices()[..]);
+}
+
+#[test]
+fn on_release_test() {
+    let (tx, rx) = mpsc::channel();
+    let chunks = ChunkMut::with_on_release(vec![0; LEN], move |owner| {
+        tx.send(owner).unwrap();
+    })
+    .into_even_chunks(4);
+
+    // the owner is received from whichever thread drops the last chunk
+    let handles: Vec<_> = chunks
+        .map(|mut chunk| {
+            thread::spawn(move || {
+                chunk
+                    .enumerate_global()
+                    .for_each(|(index, elem)| *elem = index);
+            })
+        })
+        .collect();
+    assert_eq!(rx.recv().unwrap().unwrap(), indices());
+    handles
+        .into_iter()
+        .for_each(|handle| handle.join().unwrap());
+}
+
+#[test]
+fn into_arc_owner_race_test() {
+    // the release callback keeps the owner state alive, so the owner is only synchronized
+    // through the reference count on the data
+    let (lchunk, rchunk) = ChunkMut::with_on_release(vec![0; LEN], |_| {}).split_at(LEN / 2);
+
+    // the worker is not joined, so its write is only visible through the recovery
+    thread::spawn(move || {
+        let mut rchunk = rchunk;
+        rchunk[0] = 1;
+    });
+
+    let mut lchunk = lchunk;
+    let arc = loop {
+        match lchunk.into_arc_owner() {
+            Ok(arc) => break arc,
+            Err(err) => {
+                lchunk = err.into_shared().unwrap();
+                thread::yield_now();
+            }
+        }
+    };
+    assert_eq!(arc[LEN / 2], 1);
+}
@@ -0,0 +1,62 @@
+#![cfg(feature = "tracking")]
+
+use concurrent_slice::{Chunk, ChunkMut, ChunkSet};
+use std::thread;
+
+#[test]
+fn live_chunks_test() {
+    let mut chunks = ChunkMut::new(vec![0u32; 12]).into_even_chunks(3);
+    let first = chunks.next().unwrap();
+    let second = thread::Builder::new()
+        .name("worker".into())
+        .spawn({
+            let second = chunks.next().unwrap();
+            move || {
+                let (lchunk, rchunk) = second.split_at(2);
+                (lchunk, rchunk)
+            }
+        })
+        .unwrap()
+        .join()
+        .unwrap();
+    drop(first);
+
+    // the chunks are listed in the order of creation
+    let chunks = chunks
+        .try_unwrap_owner()
+        .unwrap_err()
+        .into_shared()
+        .unwrap();
+    let live = chunks.live_chunks();
+    assert_eq!(
+        live.iter()
+            .map(|info| info.range.clone())
+            .collect::<Vec<_>>(),
+        vec![4..6, 6..8]
+    );
+    assert!(live
+        .iter()
+        .all(|info| info.thread_name.as_deref() == Some("worker")));
+    assert!(live[0]
+        .to_string()
+        .starts_with("chunk 4..6 created on thread"));
+
+    let (lchunk, rchunk) = second;
+    let mut set = ChunkSet::new();
+    set.extend(chunks);
+    set.insert(lchunk).unwrap();
+    assert_eq!(set.live_chunks().len(), 3);
+    drop(rchunk);
+
+    let array = ChunkMut::new(vec![0u32; 4]).into_array_chunks::<2>().0;
+    assert_eq!(array.live_chunks().len(), 1);
+    let chunk = array.into_chunk();
+    assert_eq!(chunk.live_chunks()[0].range, 0..4);
+    assert!(chunk.try_unwrap_owner().is_ok());
+
+    let chunk = Chunk::new(vec![0u32; 4]);
+    let weak = chunk.downgrade();
+    assert_eq!(weak.live_chunks().len(), 1);
+    drop(chunk);
+    assert!(weak.live_chunks().is_empty());
+}